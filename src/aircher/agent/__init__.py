@@ -1,10 +1,19 @@
 """Core agent implementation using LangGraph."""
 
+import asyncio
+import json
+import re
 from datetime import datetime
 from pathlib import Path
 from typing import Any, Optional
 
-from langchain_core.messages import AIMessage, BaseMessage, HumanMessage, SystemMessage
+from langchain_core.messages import (
+    AIMessage,
+    BaseMessage,
+    ChatMessage,
+    HumanMessage,
+    SystemMessage,
+)
 from langchain_core.runnables import RunnableConfig
 from langchain_openai import ChatOpenAI
 from langgraph.graph import END, START, StateGraph
@@ -14,12 +23,162 @@ from loguru import logger
 from pydantic import BaseModel
 
 from ..config import get_settings
-from ..context import ContextItemType, ContextWindow
+from ..context import ContextBudget, ContextItemType, ContextWindow
 from ..memory.integration import MemoryIntegration, create_memory_system
-from ..models import ModelRouter
+from ..models import SUPPORTED_MODELS, ModelProvider, ModelRouter
 from ..modes import AgentMode, get_mode_capabilities
-from ..tools import BashTool, ListDirectoryTool, ReadFileTool, SearchFilesTool, WriteFileTool
+from ..project import GitStatusCache, ProjectNotes, read_agent_instructions
+from ..tools import (
+    BashTool,
+    GitCommitTool,
+    ListDirectoryTool,
+    ReadFileTool,
+    SearchFilesTool,
+    ScratchpadTool,
+    WebSearchTool,
+    WriteFileTool,
+    web_search_available,
+)
 from ..tools.manager import ToolManager
+from .trace import ToolTracer
+
+
+def encode_image_attachment(image: str) -> dict[str, Any]:
+    """Encode an image (file path or base64 data) as a multimodal content block.
+
+    Args:
+        image: Path to an image file, or an already-encoded base64 string.
+
+    Returns:
+        A LangChain-style image_url content block with a data URL.
+
+    Raises:
+        ValueError: If a path is given but the file doesn't exist.
+    """
+    import base64
+    import mimetypes
+
+    path = Path(image)
+    if path.suffix.lower() in (".png", ".jpg", ".jpeg", ".gif", ".webp"):
+        if not path.exists():
+            raise ValueError(f"Image file not found: {image}")
+        mime_type = mimetypes.guess_type(path.name)[0] or "image/png"
+        data = base64.b64encode(path.read_bytes()).decode("ascii")
+    else:
+        # Assume pre-encoded base64 data
+        mime_type = "image/png"
+        data = image
+
+    return {
+        "type": "image_url",
+        "image_url": {"url": f"data:{mime_type};base64,{data}"},
+    }
+
+
+def budget_prompt_sections(sections: list[str], budget_tokens: int) -> list[str]:
+    """Select system prompt sections within an estimated token budget.
+
+    Sections come priority-ordered; once the budget is exhausted the
+    remaining ones are dropped whole (a truncated section reads worse
+    than an absent one). The first section always survives so the prompt
+    never loses its instructions. Drops are logged at debug level.
+
+    Args:
+        sections: Prompt sections, highest priority first.
+        budget_tokens: Estimated-token cap (0 disables budgeting).
+
+    Returns:
+        The surviving sections, in order.
+    """
+    if budget_tokens <= 0:
+        return list(sections)
+
+    kept: list[str] = []
+    used = 0
+    dropped: list[int] = []
+    for i, section in enumerate(sections):
+        tokens = len(section) // 4
+        if kept and used + tokens > budget_tokens:
+            dropped.append(i)
+            continue
+        kept.append(section)
+        used += tokens
+    if dropped:
+        logger.debug(
+            f"System prompt over budget ({budget_tokens} tokens): dropped "
+            f"{len(dropped)} lower-priority section(s)"
+        )
+    return kept
+
+
+def budget_system_prompt(sections: list[str], budget_tokens: int) -> str:
+    """Assemble system prompt sections within an estimated token budget.
+
+    See budget_prompt_sections for the drop rules.
+    """
+    return "\n\n".join(budget_prompt_sections(sections, budget_tokens))
+
+
+# Tools safe to run concurrently - no side effects beyond reads
+READ_ONLY_TOOLS = frozenset(
+    {"read_file", "list_directory", "search_files", "web_search"}
+)
+
+# Dangling commas before a closing bracket/brace
+_TRAILING_COMMA_RE = re.compile(r",\s*([\]}])")
+# Unquoted object keys ({tool: ...} instead of {"tool": ...})
+_UNQUOTED_KEY_RE = re.compile(r"([{,]\s*)([A-Za-z_][A-Za-z0-9_]*)(\s*:)")
+
+
+def lenient_json_loads(text: str) -> Any:
+    """Parse JSON, repairing the malformations weak models actually emit.
+
+    Strict parsing is tried first; on failure trailing commas are removed
+    and bare object keys quoted, then parsing is retried. Raises
+    json.JSONDecodeError (a ValueError) when even the repaired text won't
+    parse, so callers can feed the error back to the model.
+    """
+    try:
+        return json.loads(text)
+    except json.JSONDecodeError:
+        repaired = _TRAILING_COMMA_RE.sub(r"\1", text)
+        repaired = _UNQUOTED_KEY_RE.sub(r'\1"\2"\3', repaired)
+        return json.loads(repaired)
+
+
+def _extract_json_array(text: str) -> str:
+    """Pull the JSON array out of a model response (code fences and all)."""
+    match = re.search(r"```(?:json)?\s*(\[[\s\S]*?\])\s*```", text)
+    if match:
+        return match.group(1)
+    match = re.search(r"\[[\s\S]*\]", text)
+    return match.group(0) if match else "[]"
+
+
+def _extract_reasoning(response: Any) -> str:
+    """Pull model reasoning/thinking text out of a chat response, if any.
+
+    OpenAI-compatible endpoints surface it as ``reasoning_content`` in
+    additional_kwargs; Anthropic returns thinking content blocks inside a
+    list-valued message content.
+    """
+    extras = getattr(response, "additional_kwargs", None) or {}
+    reasoning = extras.get("reasoning_content") or extras.get("reasoning")
+    if isinstance(reasoning, str) and reasoning.strip():
+        return reasoning
+
+    content = getattr(response, "content", None)
+    if isinstance(content, list):
+        parts = [
+            block.get("thinking", "")
+            for block in content
+            if isinstance(block, dict) and block.get("type") == "thinking"
+        ]
+        joined = "\n".join(part for part in parts if part)
+        if joined.strip():
+            return joined
+
+    return ""
 
 
 class AgentState(BaseModel):
@@ -35,6 +194,7 @@ class AgentState(BaseModel):
     intent: str = ""
     tool_calls: list[dict[str, Any]] = []
     response: str = ""
+    reasoning: str = ""
 
 
 class AircherAgent:
@@ -50,20 +210,36 @@ class AircherAgent:
         self.model_name = model_name
         self.enable_memory = enable_memory
 
-        # Set up data directory
+        # Set up data directory; on read-only filesystems (containers,
+        # restricted CI) fall back to ephemeral in-memory storage instead of
+        # refusing to start
         if data_dir is None:
             data_dir = Path.home() / ".aircher" / "data"
         self.data_dir = data_dir
-        self.data_dir.mkdir(parents=True, exist_ok=True)
+        try:
+            self.data_dir.mkdir(parents=True, exist_ok=True)
+            self.storage_writable = True
+        except OSError as e:
+            self.storage_writable = False
+            logger.warning(
+                f"Data directory {self.data_dir} is not writable ({e}); "
+                "running with ephemeral in-memory storage - nothing will persist"
+            )
 
         # Initialize memory systems
         if self.enable_memory:
-            db_path = self.data_dir / "episodic.duckdb"
-            vector_dir = self.data_dir / "vectors"
-            self.memory: MemoryIntegration | None = create_memory_system(
-                db_path=db_path, vector_persist_dir=vector_dir
-            )
-            logger.info("Memory systems initialized")
+            if self.storage_writable:
+                db_path = self.data_dir / "episodic.duckdb"
+                vector_dir = self.data_dir / "vectors"
+                self.memory: MemoryIntegration | None = create_memory_system(
+                    db_path=db_path, vector_persist_dir=vector_dir
+                )
+                logger.info("Memory systems initialized")
+            else:
+                self.memory = create_memory_system(
+                    db_path=None, vector_persist_dir=None
+                )
+                logger.info("Memory systems initialized (ephemeral)")
         else:
             self.memory = None
             logger.info("Memory systems disabled")
@@ -74,6 +250,15 @@ class AircherAgent:
         # Initialize model router for smart model selection and cost tracking
         self.model_router: ModelRouter | None = None  # Created per-session
 
+        # Git state for the system prompt (None outside git repos)
+        self.git_status = GitStatusCache(Path.cwd())
+
+        # Durable per-project notes (/remember), injected into the prompt
+        self.project_notes = ProjectNotes(Path.cwd())
+
+        # JSONL tool-call audit trail (off until /trace on or --trace)
+        self.tracer = ToolTracer(Path.cwd())
+
         # Initialize tool manager for bundled tools
         self.tool_manager = ToolManager()
 
@@ -96,7 +281,12 @@ class AircherAgent:
         self.graph = self._build_graph()
 
     def _load_tools(self) -> list[Any]:
-        """Load available tools based on configuration."""
+        """Load available tools based on configuration.
+
+        Tools excluded by the configured allowlist/denylist are recorded in
+        self.disabled_tools so invocation attempts get a clear error instead
+        of "not found".
+        """
         tools = []
 
         # Command execution tool (needed by SearchFilesTool)
@@ -108,8 +298,32 @@ class AircherAgent:
         tools.append(WriteFileTool())
         tools.append(ListDirectoryTool())
         tools.append(SearchFilesTool(bash_tool))
+        tools.append(GitCommitTool())
+
+        # Sandboxed notes file for multi-step plans; confined to
+        # .aircher/scratch.md so it needs no write approval
+        tools.append(ScratchpadTool())
+
+        # Web search is opt-in and needs a search API key; modes without
+        # network access never see it regardless
+        if self.settings.web_search_enabled and web_search_available():
+            tools.append(WebSearchTool())
+
+        allowlist = self.settings.tool_allowlist
+        denylist = self.settings.tool_denylist
+
+        enabled = []
+        self.disabled_tools: set[str] = set()
+        for tool in tools:
+            if (allowlist and tool.name not in allowlist) or tool.name in denylist:
+                self.disabled_tools.add(tool.name)
+            else:
+                enabled.append(tool)
 
-        return tools
+        if self.disabled_tools:
+            logger.info(f"Tools disabled by configuration: {sorted(self.disabled_tools)}")
+
+        return enabled
 
     def _build_graph(self) -> Any:
         """Build the LangGraph workflow with conditional edges."""
@@ -231,8 +445,8 @@ class AircherAgent:
         # Simple intent classification (can be enhanced with ML)
         intent = self._classify_intent_simple(str(user_request))
 
-        # Enhance with memory context if available
-        if self.memory:
+        # Enhance with memory context if available (skipped in no-context mode)
+        if self.memory and state.context.get("include_context", True):
             try:
                 # Check if we've seen similar requests before
                 # This would use vector search in production
@@ -330,9 +544,10 @@ class AircherAgent:
         # Get mode capabilities
         capabilities = get_mode_capabilities(state.current_mode)
 
-        # Query memory for context to inform tool selection
+        # Query memory for context to inform tool selection (skipped in
+        # no-context mode)
         memory_context = {}
-        if self.memory:
+        if self.memory and state.context.get("include_context", True):
             try:
                 # Extract file paths from request
                 files_mentioned = self._extract_file_paths(user_request)
@@ -376,6 +591,15 @@ class AircherAgent:
         state.tool_calls = tool_calls
         state.tools_available = [tool.name for tool in self.tools]
         state.context["tool_plan"] = tool_calls
+        # The plan captures the model's reasoning between tool calls
+        self.tracer.record(
+            {
+                "type": "plan",
+                "session_id": state.session_id,
+                "intent": intent,
+                "tool_calls": tool_calls,
+            }
+        )
         return state
 
     def _extract_file_paths(self, text: str) -> list[str]:
@@ -406,6 +630,12 @@ class AircherAgent:
         if not self.llm:
             return self._generate_tool_plan_fallback(intent, request, capabilities)
 
+        # Models flagged as lacking tool support get rule-based planning
+        # instead of being asked to emit structured JSON they can't produce
+        model_config = SUPPORTED_MODELS.get(self.model_name)
+        if model_config is not None and not model_config.supports_tools:
+            return self._generate_tool_plan_fallback(intent, request, capabilities)
+
         # Get available tools based on capabilities
         available_tools = self._get_available_tools_for_capabilities(capabilities)
 
@@ -449,21 +679,25 @@ Tool plan:"""
             # Use LLM to plan tool calls
             response = self.llm.invoke(planning_prompt)
             response_text = response.content
+            self._record_llm_usage(response)
 
-            # Extract JSON from response (handle markdown code blocks)
-            import json as json_module
-            import re
-
-            # Try to find JSON in code blocks first
-            json_match = re.search(r'```(?:json)?\s*(\[[\s\S]*?\])\s*```', response_text)
-            if json_match:
-                json_text = json_match.group(1)
-            else:
-                # Try to find JSON array directly
-                json_match = re.search(r'\[[\s\S]*\]', response_text)
-                json_text = json_match.group(0) if json_match else "[]"
+            try:
+                tool_calls = lenient_json_loads(_extract_json_array(response_text))
+            except json.JSONDecodeError as parse_error:
+                # Feed the parse error back once so the model can
+                # self-correct instead of aborting the turn
+                logger.warning(
+                    f"Tool plan JSON invalid ({parse_error}); asking model to fix"
+                )
+                retry = self.llm.invoke(
+                    f"{planning_prompt}\n\n"
+                    f"Your tool call JSON was invalid: {parse_error}\n"
+                    f"Your response was:\n{response_text}\n\n"
+                    "Respond again with ONLY a valid JSON array of tool calls."
+                )
+                self._record_llm_usage(retry)
+                tool_calls = lenient_json_loads(_extract_json_array(retry.content))
 
-            tool_calls = json_module.loads(json_text)
             logger.info(f"LLM generated {len(tool_calls)} tool calls")
 
         except Exception as e:
@@ -515,88 +749,178 @@ Tool plan:"""
             if tool.name in ["read_file", "list_directory", "search_files"]:
                 if capabilities.can_read_files:
                     available_tools.append(tool)
-            elif tool.name in ["write_file"]:
+            elif tool.name in ["write_file", "git_commit"]:
                 if capabilities.can_write_files:
                     available_tools.append(tool)
             elif tool.name in ["bash"]:
                 if capabilities.can_execute_commands:
                     available_tools.append(tool)
+            elif tool.name in ["web_search"]:
+                if capabilities.can_access_network:
+                    available_tools.append(tool)
+            elif tool.name in ["scratchpad"]:
+                # Confined to .aircher/scratch.md, so safe in every mode
+                # including read-only
+                available_tools.append(tool)
 
         return available_tools
 
     async def _execute_task(self, state: AgentState) -> AgentState:
-        """Execute the task using selected tools."""
+        """Execute the task using selected tools.
+
+        Consecutive read-only calls run concurrently up to
+        settings.tool_concurrency, since they can't interfere with each
+        other. Writes and commands always run alone, in plan order.
+        """
         tool_calls = state.tool_calls
-        results = []
+        results: list[dict[str, Any]] = []
 
         # Find tools by name
         tool_map = {tool.name: tool for tool in self.tools}
 
-        for tool_call in tool_calls:
-            tool_name = tool_call["tool"]
-            parameters = tool_call["parameters"]
+        concurrency = max(self.settings.tool_concurrency, 1)
+        index = 0
+        while index < len(tool_calls):
+            if concurrency > 1 and tool_calls[index]["tool"] in READ_ONLY_TOOLS:
+                # Batch the run of read-only calls starting here
+                batch = [tool_calls[index]]
+                while (
+                    index + len(batch) < len(tool_calls)
+                    and tool_calls[index + len(batch)]["tool"] in READ_ONLY_TOOLS
+                ):
+                    batch.append(tool_calls[index + len(batch)])
+
+                if len(batch) > 1:
+                    logger.info(
+                        f"Running {len(batch)} read-only tools concurrently "
+                        f"(cap {concurrency})"
+                    )
+                semaphore = asyncio.Semaphore(concurrency)
+
+                async def bounded(tool_call: dict[str, Any]) -> dict[str, Any]:
+                    async with semaphore:
+                        return await self._run_tool_call(state, tool_map, tool_call)
+
+                batch_results = await asyncio.gather(*(bounded(c) for c in batch))
+                if len(batch) > 1:
+                    # Let status displays mark these as having run together
+                    for record in batch_results:
+                        record["concurrent"] = True
+                results.extend(batch_results)
+                index += len(batch)
+            else:
+                results.append(
+                    await self._run_tool_call(state, tool_map, tool_calls[index])
+                )
+                index += 1
 
-            start_time = datetime.now()
+        # Update state with results
+        state.metadata["tool_results"] = results
+        state.context["execution_complete"] = True
+        return state
 
-            try:
-                # Find the tool
-                tool = tool_map.get(tool_name)
-                if not tool:
-                    logger.warning(f"Tool {tool_name} not found")
-                    results.append(
-                        {
-                            "tool": tool_name,
-                            "parameters": parameters,
-                            "error": f"Tool {tool_name} not found",
-                        }
-                    )
-                    continue
+    async def _run_tool_call(
+        self,
+        state: AgentState,
+        tool_map: dict[str, Any],
+        tool_call: dict[str, Any],
+    ) -> dict[str, Any]:
+        """Run one planned tool call and return its result record."""
+        tool_name = tool_call["tool"]
+        parameters = tool_call["parameters"]
+
+        start_time = datetime.now()
+
+        # Disabled tools get a distinct, clear error
+        if tool_name in self.disabled_tools:
+            logger.warning(f"Tool {tool_name} is disabled by configuration")
+            return {
+                "tool": tool_name,
+                "parameters": parameters,
+                "error": f"Tool {tool_name} is disabled by configuration",
+            }
 
-                # Execute the tool
-                logger.info(f"Executing tool {tool_name} with parameters {parameters}")
+        # Find the tool
+        tool = tool_map.get(tool_name)
+        if not tool:
+            logger.warning(f"Tool {tool_name} not found")
+            return {
+                "tool": tool_name,
+                "parameters": parameters,
+                "error": f"Tool {tool_name} not found",
+            }
 
-                # Wrap memory tracking if enabled
-                if self.memory:
-                    tool_func = self.memory.track_tool_execution(tool.execute)
-                else:
-                    tool_func = tool.execute
+        try:
+            # Execute the tool
+            logger.info(f"Executing tool {tool_name} with parameters {parameters}")
 
-                # Execute (handle both sync and async)
-                if hasattr(tool.execute, "__call__"):
-                    result = await tool_func(**parameters)
-                else:
-                    result = await tool_func(**parameters)
+            # Wrap memory tracking if enabled
+            if self.memory:
+                tool_func = self.memory.track_tool_execution(tool.execute)
+            else:
+                tool_func = tool.execute
 
-                duration_ms = int((datetime.now() - start_time).total_seconds() * 1000)
+            result = await tool_func(**parameters)
 
-                results.append(
-                    {
-                        "tool": tool_name,
-                        "parameters": parameters,
-                        "result": result,
-                        "duration_ms": duration_ms,
-                    }
-                )
+            duration_ms = int((datetime.now() - start_time).total_seconds() * 1000)
 
-                logger.info(f"Tool {tool_name} completed in {duration_ms}ms")
+            record = {
+                "tool": tool_name,
+                "parameters": parameters,
+                "result": result,
+                "duration_ms": duration_ms,
+            }
+            self.tracer.record(
+                {
+                    "type": "tool_call",
+                    "session_id": state.session_id,
+                    "tool": tool_name,
+                    "parameters": parameters,
+                    "result": result,
+                    "duration_ms": duration_ms,
+                }
+            )
 
-            except Exception as e:
-                duration_ms = int((datetime.now() - start_time).total_seconds() * 1000)
-                logger.error(f"Tool {tool_name} failed: {e}")
+            logger.info(f"Tool {tool_name} completed in {duration_ms}ms")
+            return record
 
-                results.append(
-                    {
-                        "tool": tool_name,
-                        "parameters": parameters,
-                        "error": str(e),
-                        "duration_ms": duration_ms,
-                    }
-                )
+        except Exception as e:
+            duration_ms = int((datetime.now() - start_time).total_seconds() * 1000)
+            logger.error(f"Tool {tool_name} failed: {e}")
 
-        # Update state with results
-        state.metadata["tool_results"] = results
-        state.context["execution_complete"] = True
-        return state
+            self.tracer.record(
+                {
+                    "type": "tool_call",
+                    "session_id": state.session_id,
+                    "tool": tool_name,
+                    "parameters": parameters,
+                    "error": str(e),
+                    "duration_ms": duration_ms,
+                }
+            )
+            return {
+                "tool": tool_name,
+                "parameters": parameters,
+                "error": str(e),
+                "duration_ms": duration_ms,
+            }
+
+    def _record_llm_usage(self, response: Any) -> None:
+        """Feed token counts from a chat response into the cost tracker."""
+        if not self.model_router:
+            return
+        usage = getattr(response, "usage_metadata", None) or {}
+        if not usage:
+            return
+        input_details = usage.get("input_token_details") or {}
+        output_details = usage.get("output_token_details") or {}
+        self.model_router.track_usage(
+            self.model_name,
+            input_tokens=usage.get("input_tokens", 0),
+            output_tokens=usage.get("output_tokens", 0),
+            reasoning_tokens=output_details.get("reasoning", 0),
+            cached_input_tokens=input_details.get("cache_read", 0),
+        )
 
     async def _generate_response(self, state: AgentState) -> AgentState:
         """Generate final response to the user using LLM."""
@@ -659,6 +983,22 @@ Response:"""
             # Generate response with LLM
             response = self.llm.invoke(response_prompt)
             response_text = response.content
+            self._record_llm_usage(response)
+
+            # Surface any reasoning/thinking text so the UI can render it
+            # alongside the answer
+            reasoning = _extract_reasoning(response)
+            if reasoning:
+                state.reasoning = reasoning
+
+            # Surface refusal/safety stops so the UI can explain an empty
+            # reply instead of showing a blank message
+            response_metadata = getattr(response, "response_metadata", None) or {}
+            finish_reason = response_metadata.get(
+                "finish_reason"
+            ) or response_metadata.get("stop_reason")
+            if finish_reason:
+                state.metadata["finish_reason"] = finish_reason
 
             logger.info("Generated LLM response")
             state.response = response_text
@@ -775,13 +1115,149 @@ Response:"""
 
         return state
 
+    def _system_prompt_messages(
+        self, system_prompt_override: str | None = None
+    ) -> list[BaseMessage]:
+        """Assemble the system prompt messages for a request.
+
+        Sections in priority order: instructions always survive, git
+        state and project notes are dropped first under the budget.
+        """
+        sections = [
+            system_prompt_override
+            or "You are Aircher, an intelligent coding assistant "
+            "with memory capabilities."
+        ]
+        # Project instruction file (AGENT.md, or another tool's
+        # equivalent - see project.INSTRUCTION_FILES for precedence)
+        instructions = read_agent_instructions(Path.cwd())
+        if instructions:
+            name, content = instructions
+            sections.append(f"Project instructions (from {name}):\n{content}")
+        # Git state so the agent knows which branch it's on and whether
+        # local edits exist (silently absent outside git repos)
+        git_state = self.git_status.get()
+        if git_state:
+            sections.append(git_state.summary())
+        notes = self.project_notes.render()
+        if notes:
+            sections.append(notes)
+        kept_sections = budget_prompt_sections(
+            sections, self.settings.system_prompt_token_budget
+        )
+        prompt_model = SUPPORTED_MODELS.get(self.model_name)
+        prompt_messages: list[BaseMessage]
+        if prompt_model is not None and prompt_model.provider == ModelProvider.OPENAI:
+            # OpenAI distinguishes developer-role instructions from
+            # ambient system notes; send instructions as developer and
+            # the rest (git state, project notes) as system
+            prompt_messages = [ChatMessage(role="developer", content=kept_sections[0])]
+            if len(kept_sections) > 1:
+                prompt_messages.append(
+                    SystemMessage(content="\n\n".join(kept_sections[1:]))
+                )
+        else:
+            # Providers without a developer role get everything folded
+            # into one system message
+            prompt_text = "\n\n".join(kept_sections)
+            # Anthropic only caches explicitly marked prefixes (and only
+            # past ~1024 tokens); OpenAI caches long prefixes automatically
+            prompt_content: Any = prompt_text
+            if (
+                self.settings.prompt_caching
+                and prompt_model is not None
+                and prompt_model.provider == ModelProvider.ANTHROPIC
+                and len(prompt_text) // 4 >= 1024
+            ):
+                prompt_content = [
+                    {
+                        "type": "text",
+                        "text": prompt_text,
+                        "cache_control": {"type": "ephemeral"},
+                    }
+                ]
+            prompt_messages = [SystemMessage(content=prompt_content)]
+        return prompt_messages
+
+    def export_context(
+        self, message: str, system_prompt_override: str | None = None
+    ) -> list[dict[str, str]]:
+        """Assemble the context a message would send, without a provider call.
+
+        Returns role/content pairs in send order for prompt debugging
+        (aircher export-context): the same system prompt assembly as a
+        real request, then the user message.
+        """
+        exported: list[dict[str, str]] = []
+        for prompt_message in self._system_prompt_messages(system_prompt_override):
+            role = getattr(prompt_message, "role", None) or prompt_message.type
+            content = prompt_message.content
+            if not isinstance(content, str):
+                # Cache-control wrapped prefix: unwrap back to plain text
+                content = "\n\n".join(
+                    block.get("text", "")
+                    for block in content
+                    if isinstance(block, dict)
+                )
+            exported.append({"role": role, "content": content})
+        exported.append({"role": "user", "content": message})
+        return exported
+
     async def run(
         self,
         message: str,
         mode: AgentMode = AgentMode.READ,
         session_id: str | None = None,
+        include_context: bool = True,
+        images: list[str] | None = None,
+        history: list[dict[str, str]] | None = None,
+        system_prompt_override: str | None = None,
+        temperature: float | None = None,
+        reasoning_effort: str | None = None,
+        inspect: bool = False,
     ) -> dict[str, Any]:
-        """Run the agent with a message."""
+        """Run the agent with a message.
+
+        Args:
+            message: The user request.
+            mode: Agent operation mode.
+            session_id: Optional session ID (auto-generated if not provided).
+            include_context: When False, skip the system prompt and memory
+                context enrichment - the message is sent as-is. Useful for
+                quick factual questions unrelated to the current project.
+            images: Optional image attachments (file paths or base64 data)
+                for vision-capable models.
+            history: Prior conversation turns as {"role", "content"} dicts.
+                Only the most recent settings.max_history_messages entries
+                are sent, capping per-request cost regardless of how much
+                scrollback the caller keeps.
+            system_prompt_override: Replaces the default assistant identity
+                in the system prompt. Git state and project notes are still
+                appended, so specialized sessions keep project awareness.
+            temperature: Sampling temperature for this request; falls back
+                to settings.temperature when not given.
+            reasoning_effort: low/medium/high thinking budget for reasoning
+                models; falls back to settings.reasoning_effort.
+            inspect: Hard read-only guarantee - forces READ capabilities
+                for this request regardless of the requested mode, so
+                write/execute tools are never even offered to the model.
+
+        Raises:
+            ValueError: If images are attached but the model lacks vision
+                support, or an image file doesn't exist.
+        """
+        if inspect:
+            # Capability filtering (permission validation and tool
+            # selection) keys off the mode, so forcing READ disables
+            # mutations everywhere downstream - no approval path exists
+            mode = AgentMode.READ
+        if images:
+            model_config = SUPPORTED_MODELS.get(self.model_name)
+            if model_config is None or not model_config.supports_vision:
+                raise ValueError(
+                    f"Model {self.model_name} does not support image attachments; "
+                    "use a vision-capable model (e.g. gpt-4o, claude-sonnet)"
+                )
         if session_id is None:
             session_id = f"session_{datetime.now().strftime('%Y%m%d_%H%M%S')}"
 
@@ -797,7 +1273,10 @@ Response:"""
         try:
             self.llm = self.model_router.get_model(
                 model_name=self.model_router.select_model_for_task("main_agent"),
-                temperature=0.7,
+                temperature=(
+                    temperature if temperature is not None else self.settings.temperature
+                ),
+                reasoning_effort=reasoning_effort or self.settings.reasoning_effort,
             )
             logger.info(f"Using model: {self.model_name} for main agent")
         except Exception as e:
@@ -810,31 +1289,55 @@ Response:"""
         self.context_window = ContextWindow(
             session_id=session_id,
             memory=self.memory,
+            budget=ContextBudget(**self.settings.context_budget.model_dump()),
         )
         logger.info(f"Initialized context window for session {session_id}")
 
-        # Add system prompt as sticky item
-        system_prompt = SystemMessage(
-            content="You are Aircher, an intelligent coding assistant with memory capabilities."
-        )
-        self.context_window.add_item(
-            item_type=ContextItemType.SYSTEM_PROMPT,
-            content=system_prompt,
-            sticky=True,  # Never remove system prompt
-        )
-
-        # Add user message
-        user_msg = HumanMessage(content=message)
+        # Add system prompt as sticky item (skipped in no-context mode)
+        messages: list[BaseMessage] = []
+        if include_context:
+            for prompt_message in self._system_prompt_messages(system_prompt_override):
+                self.context_window.add_item(
+                    item_type=ContextItemType.SYSTEM_PROMPT,
+                    content=prompt_message,
+                    sticky=True,  # Never remove system prompt
+                )
+                messages.append(prompt_message)
+
+        # Replay recent conversation history (windowed by config)
+        if history:
+            cap = self.settings.max_history_messages
+            if cap > 0:
+                history = history[-cap:]
+            for turn in history:
+                if turn.get("role") == "assistant":
+                    prior: BaseMessage = AIMessage(content=turn.get("content", ""))
+                    item_type = ContextItemType.ASSISTANT_RESPONSE
+                else:
+                    prior = HumanMessage(content=turn.get("content", ""))
+                    item_type = ContextItemType.USER_MESSAGE
+                self.context_window.add_item(item_type=item_type, content=prior)
+                messages.append(prior)
+
+        # Add user message (multimodal content when images are attached)
+        if images:
+            content: Any = [{"type": "text", "text": message}]
+            content.extend(encode_image_attachment(image) for image in images)
+            user_msg = HumanMessage(content=content)
+        else:
+            user_msg = HumanMessage(content=message)
         self.context_window.add_item(
             item_type=ContextItemType.USER_MESSAGE,
             content=user_msg,
         )
+        messages.append(user_msg)
 
         initial_state = AgentState(
             current_mode=mode,
-            messages=[system_prompt, user_msg],
+            messages=messages,
             session_id=session_id,
             user_request=message,
+            context={"include_context": include_context},
         )
 
         config = RunnableConfig(
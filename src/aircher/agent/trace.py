@@ -0,0 +1,40 @@
+"""Machine-readable tool-invocation tracing.
+
+Distinct from user-facing tool-status messages: when enabled, every tool
+call is appended as one JSON line - name, arguments, result or error, and
+duration - giving a complete audit trail for debugging agent behavior
+(especially unexpected turbo-mode actions).
+"""
+
+import json
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from loguru import logger
+
+
+class ToolTracer:
+    """Append-only JSONL trace of agent tool activity.
+
+    Disabled by default; toggled via /trace in the TUI or --trace on the
+    CLI. The trace lives under the project dir so it travels with the
+    work being debugged.
+    """
+
+    def __init__(self, project_dir: Path | None = None, enabled: bool = False):
+        project_dir = project_dir or Path.cwd()
+        self.trace_path = project_dir / ".aircher" / "trace.jsonl"
+        self.enabled = enabled
+
+    def record(self, event: dict[str, Any]) -> None:
+        """Append one event to the trace (no-op while disabled)."""
+        if not self.enabled:
+            return
+        entry = {"timestamp": datetime.now().isoformat(), **event}
+        try:
+            self.trace_path.parent.mkdir(parents=True, exist_ok=True)
+            with self.trace_path.open("a", encoding="utf-8") as f:
+                f.write(json.dumps(entry, default=str) + "\n")
+        except OSError as e:
+            logger.warning(f"Failed to write tool trace: {e}")
@@ -0,0 +1,106 @@
+"""Provider credential storage and import."""
+
+import json
+import os
+from pathlib import Path
+
+from loguru import logger
+
+# Providers and the environment variables their API keys live in
+PROVIDER_ENV_VARS = {
+    "openai": "OPENAI_API_KEY",
+    "anthropic": "ANTHROPIC_API_KEY",
+    "openrouter": "OPENROUTER_API_KEY",
+}
+
+
+def mask_key(key: str) -> str:
+    """Mask a key for display; full values never appear in output or logs."""
+    if len(key) <= 12:
+        return "***"
+    return f"{key[:6]}...{key[-4:]}"
+
+
+class AuthStore:
+    """API keys persisted in the user's aircher directory.
+
+    Keys are applied back to the environment at startup so the provider
+    SDKs (which read env vars) pick them up without per-shell exports.
+    """
+
+    def __init__(self, store_path: Path | None = None):
+        self.store_path = store_path or Path.home() / ".aircher" / "credentials.json"
+        self._keys: dict[str, str] = self._load()
+
+    def _load(self) -> dict[str, str]:
+        try:
+            return json.loads(self.store_path.read_text())
+        except (OSError, ValueError):
+            return {}
+
+    def save(self) -> None:
+        """Persist the store, readable only by the owner."""
+        try:
+            self.store_path.parent.mkdir(parents=True, exist_ok=True)
+            self.store_path.write_text(json.dumps(self._keys, indent=2))
+            self.store_path.chmod(0o600)
+        except OSError as e:
+            logger.warning(f"Failed to save credentials: {e}")
+
+    def set_key(self, provider: str, key: str) -> None:
+        """Store a provider key (in memory until save())."""
+        self._keys[provider] = key
+
+    def get_key(self, provider: str) -> str | None:
+        """Get a stored provider key."""
+        return self._keys.get(provider)
+
+    def providers(self) -> list[str]:
+        """Providers with stored keys."""
+        return sorted(self._keys)
+
+    def apply_to_environment(self) -> None:
+        """Export stored keys as env vars, without overriding existing ones."""
+        for provider, env_var in PROVIDER_ENV_VARS.items():
+            key = self._keys.get(provider)
+            if key and not os.environ.get(env_var):
+                os.environ[env_var] = key
+
+    def discover(
+        self,
+        env: dict[str, str] | None = None,
+        project_dir: Path | None = None,
+    ) -> dict[str, str]:
+        """Find provider keys in the environment and a local .env file.
+
+        Returns:
+            {provider: key} for every recognized key found (env vars win
+            over .env entries).
+        """
+        env = dict(os.environ) if env is None else env
+        project_dir = project_dir or Path.cwd()
+
+        found: dict[str, str] = {}
+        dotenv = _parse_dotenv(project_dir / ".env")
+        for provider, env_var in PROVIDER_ENV_VARS.items():
+            key = env.get(env_var) or dotenv.get(env_var)
+            if key:
+                found[provider] = key
+        return found
+
+
+def _parse_dotenv(path: Path) -> dict[str, str]:
+    """Minimal KEY=VALUE parsing of a .env file (no dependency needed)."""
+    try:
+        lines = path.read_text().splitlines()
+    except OSError:
+        return {}
+
+    values: dict[str, str] = {}
+    for line in lines:
+        line = line.strip()
+        if not line or line.startswith("#") or "=" not in line:
+            continue
+        key, _, value = line.partition("=")
+        values[key.strip()] = value.strip().strip("\"'")
+    return values
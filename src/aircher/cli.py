@@ -1,17 +1,70 @@
 """Command-line interface for Aircher."""
 
 import asyncio
+import json as json_module
+import math
+import os
+import sys
+from pathlib import Path
+from typing import Any
 
 import click
 
 from . import __version__
 
 
+def _default_data_dir() -> Path:
+    """Default data directory (matches AircherAgent)."""
+    return Path.home() / ".aircher" / "data"
+
+
+# Cap piped input so a runaway pipe can't blow the context window
+STDIN_CHAR_LIMIT = 200_000
+
+
+def _read_piped_stdin() -> str:
+    """Read piped stdin for one-shot mode (empty when stdin is a TTY)."""
+    if sys.stdin.isatty():
+        return ""
+    data = sys.stdin.read(STDIN_CHAR_LIMIT + 1)
+    if len(data) > STDIN_CHAR_LIMIT:
+        data = data[:STDIN_CHAR_LIMIT] + "\n... [stdin truncated]"
+    return data.strip()
+
+
+def _download_progress(description: str, done: int, total: int) -> None:
+    """Report embedding-model download progress on stderr."""
+    if total:
+        percent = done * 100 // total
+        click.echo(
+            f"\rDownloading {description}: {done // 1048576}/{total // 1048576} MB "
+            f"({percent}%)",
+            err=True,
+            nl=done >= total,
+        )
+    else:
+        click.echo(f"\rDownloading {description}: {done // 1048576} MB", err=True, nl=False)
+
+
 @click.group()
 @click.version_option(version=__version__)
-def main() -> None:
+@click.option(
+    "--quiet",
+    "-q",
+    is_flag=True,
+    default=False,
+    help="Suppress cost/token summaries and non-error stderr output",
+)
+@click.pass_context
+def main(ctx: click.Context, quiet: bool) -> None:
     """Aircher: Intelligent ACP-compatible coding agent backend."""
-    pass
+    ctx.ensure_object(dict)
+    ctx.obj["quiet"] = quiet
+
+    # Stored credentials become env vars so provider SDKs find them
+    from .auth import AuthStore
+
+    AuthStore().apply_to_environment()
 
 
 @main.command()
@@ -23,17 +76,1083 @@ def status() -> None:
 
 
 @main.command()
+@click.argument("message", required=False, default="")
 @click.option(
     "--model",
     default="gpt-4o-mini",
     help="LLM model to use (default: gpt-4o-mini)",
 )
+@click.option(
+    "--mode",
+    type=click.Choice(["read", "edit", "turbo"]),
+    default="read",
+    help="Agent mode (default: read)",
+)
+@click.option(
+    "--json",
+    "json_output",
+    is_flag=True,
+    default=False,
+    help="Emit the full result as JSON on stdout",
+)
 @click.option(
     "--enable-memory/--no-memory",
     default=True,
     help="Enable/disable memory systems (default: enabled)",
 )
-def serve(model: str, enable_memory: bool) -> None:
+@click.option(
+    "--no-context",
+    is_flag=True,
+    default=False,
+    help="Skip system prompt and memory context injection (raw request)",
+)
+@click.option(
+    "--image",
+    "images",
+    multiple=True,
+    help="Attach an image file (vision-capable models only, repeatable)",
+)
+@click.option(
+    "--output",
+    "-o",
+    type=click.Path(path_type=Path),
+    default=None,
+    help="Write the response to a file instead of stdout",
+)
+@click.option(
+    "--force",
+    is_flag=True,
+    default=False,
+    help="Overwrite the --output file if it exists",
+)
+@click.option(
+    "--trace",
+    is_flag=True,
+    default=False,
+    help="Write a JSONL trace of every tool call to .aircher/trace.jsonl",
+)
+@click.option(
+    "--context-files",
+    default=None,
+    help="Comma-separated files to inject as context (budget-elided)",
+)
+@click.option(
+    "--stream-format",
+    type=click.Choice(["text", "jsonl"]),
+    default="text",
+    help="Output format: text, or jsonl (one typed JSON event per line)",
+)
+@click.option(
+    "--system",
+    "system",
+    default=None,
+    help="Custom system prompt for this request",
+)
+@click.option(
+    "--system-file",
+    type=click.Path(path_type=Path),
+    default=None,
+    help="Read the custom system prompt from a file",
+)
+@click.pass_context
+def run(
+    ctx: click.Context,
+    message: str,
+    model: str,
+    mode: str,
+    json_output: bool,
+    enable_memory: bool,
+    no_context: bool,
+    images: tuple[str, ...],
+    output: Path | None,
+    force: bool,
+    trace: bool,
+    context_files: str | None,
+    stream_format: str,
+    system: str | None,
+    system_file: Path | None,
+) -> None:
+    """Run a single agent request and print the response (one-shot mode).
+
+    Example:
+        aircher run "Explain what src/main.py does"
+        aircher --quiet run --json "Summarize this project"
+    """
+    quiet = ctx.obj.get("quiet", False)
+    if system and system_file:
+        raise click.ClickException("--system and --system-file are mutually exclusive")
+    if system_file is not None:
+        try:
+            system = system_file.read_text(encoding="utf-8").strip()
+        except OSError as e:
+            raise click.ClickException(f"Failed to read {system_file}: {e}") from e
+    handle_one_shot(
+        message=message,
+        model=model,
+        mode=mode,
+        json_output=json_output,
+        enable_memory=enable_memory,
+        quiet=quiet,
+        include_context=not no_context,
+        images=list(images),
+        output=output,
+        force=force,
+        trace=trace,
+        context_files=context_files,
+        stream_format=stream_format,
+        system_prompt=system,
+    )
+
+
+def handle_one_shot(
+    message: str,
+    model: str,
+    mode: str,
+    json_output: bool,
+    enable_memory: bool,
+    quiet: bool,
+    include_context: bool = True,
+    images: list[str] | None = None,
+    output: Path | None = None,
+    force: bool = False,
+    trace: bool = False,
+    context_files: str | None = None,
+    stream_format: str = "text",
+    system_prompt: str | None = None,
+) -> None:
+    """Execute a one-shot agent request and print results.
+
+    The model response goes to stdout (or the full JSON payload with --json),
+    or to the --output file when given. Cost/token summaries go to stderr
+    unless --quiet is set, so stdout stays clean for piping.
+
+    Piped stdin (cat error.log | aircher "explain this") is appended to
+    the message; with no message argument it becomes the whole prompt.
+    """
+    from .agent import AircherAgent
+    from .modes import AgentMode
+
+    piped = _read_piped_stdin()
+    if piped and message:
+        message = f"{message}\n\n--- piped input ---\n{piped}"
+    elif piped:
+        message = piped
+    if not message:
+        raise click.ClickException("No prompt: pass MESSAGE or pipe input on stdin")
+
+    # --context-files mirrors the TUI's @file injection for scripted use
+    if context_files:
+        message = _inject_context_files(message, context_files)
+
+    # jsonl owns stdout entirely - mixing it with --json/--output would
+    # leave consumers guessing which lines are events
+    if stream_format == "jsonl" and (json_output or output is not None):
+        raise click.ClickException(
+            "--stream-format jsonl cannot be combined with --json or --output"
+        )
+
+    # Fail before spending tokens if the output target is unwritable
+    if output is not None and output.exists() and not force:
+        raise click.ClickException(
+            f"{output} exists; use --force to overwrite"
+        )
+
+    agent = AircherAgent(model_name=model, enable_memory=enable_memory)
+    if trace:
+        agent.tracer.enabled = True
+
+    try:
+        result = asyncio.run(
+            agent.run(
+                message=message,
+                mode=AgentMode(mode),
+                include_context=include_context,
+                images=images,
+                system_prompt_override=system_prompt,
+            )
+        )
+    except ValueError as e:
+        if stream_format == "jsonl":
+            click.echo(json_module.dumps({"type": "error", "message": str(e)}))
+            sys.exit(1)
+        raise click.ClickException(str(e)) from e
+
+    if stream_format == "jsonl":
+        _emit_jsonl_events(result)
+        return
+
+    response = result.get("response", "")
+    cost_summary = result.get("cost_summary", {})
+
+    if json_output:
+        payload = {
+            "response": response,
+            "cost_summary": cost_summary,
+            "session_id": result.get("session_id", ""),
+        }
+        text = json_module.dumps(payload)
+    else:
+        text = response
+
+    if output is not None:
+        try:
+            output.write_text(text + "\n", encoding="utf-8")
+        except OSError as e:
+            raise click.ClickException(f"Failed to write {output}: {e}") from e
+        if not quiet:
+            click.echo(f"Wrote response to {output}", err=True)
+    else:
+        click.echo(text)
+
+    # Cost/token info is decoration, not output - stderr only, and
+    # suppressed entirely in quiet mode for script-friendly usage.
+    if not quiet and cost_summary:
+        savings = cost_summary.get("cache_savings", 0.0)
+        cache_note = f", saved ${savings:.4f} via prompt cache" if savings else ""
+        click.echo(
+            f"Cost: ${cost_summary.get('total_cost', 0.0):.4f} "
+            f"({cost_summary.get('total_tokens', 0)} tokens, "
+            f"{cost_summary.get('call_count', 0)} calls{cache_note})",
+            err=True,
+        )
+
+
+def _emit_jsonl_events(result: dict[str, Any]) -> None:
+    """Emit a one-shot result as typed JSONL events on stdout.
+
+    One object per line: a ``tool`` event per tool the agent ran, the
+    response as a ``chunk`` event, then a final ``done`` event with token
+    and cost totals. The response arrives as a single chunk today; the
+    event shape leaves room for finer-grained chunks once the agent path
+    streams.
+    """
+    for tool_result in result.get("metadata", {}).get("tool_results", []):
+        event: dict[str, Any] = {
+            "type": "tool",
+            "name": tool_result.get("tool", ""),
+            "ok": "error" not in tool_result,
+            "duration_ms": tool_result.get("duration_ms", 0),
+        }
+        if "error" in tool_result:
+            event["error"] = tool_result["error"]
+        click.echo(json_module.dumps(event))
+
+    click.echo(
+        json_module.dumps({"type": "chunk", "content": result.get("response", "")})
+    )
+
+    cost_summary = result.get("cost_summary", {})
+    click.echo(
+        json_module.dumps(
+            {
+                "type": "done",
+                "tokens": cost_summary.get("total_tokens", 0),
+                "cost": cost_summary.get("total_cost", 0.0),
+            }
+        )
+    )
+
+
+def _inject_context_files(message: str, context_files: str) -> str:
+    """Prepend the listed files (comma-separated) to a one-shot message.
+
+    Each file goes through the budget-aware read helper so oversized
+    files are middle-elided instead of blowing the context window.
+    Missing files fail before any tokens are spent.
+    """
+    from .context import read_for_context
+
+    blocks: list[str] = []
+    for name in context_files.split(","):
+        name = name.strip()
+        if not name:
+            continue
+        path = Path(name)
+        if not path.is_file():
+            raise click.ClickException(f"Context file not found: {name}")
+        try:
+            content, elided = read_for_context(path)
+        except OSError as e:
+            raise click.ClickException(f"Failed to read {name}: {e}") from e
+        note = " (middle elided)" if elided else ""
+        blocks.append(f"--- {name}{note} ---\n{content}")
+
+    if not blocks:
+        return message
+    return "\n\n".join(blocks) + f"\n\n{message}"
+
+
+@main.group()
+def search() -> None:
+    """Semantic code search over the project index."""
+    pass
+
+
+@search.command("index")
+@click.argument("path", default=".")
+@click.option(
+    "--incremental",
+    is_flag=True,
+    default=False,
+    help="Only reindex files changed in git (and purge deleted ones)",
+)
+@click.option(
+    "--ref",
+    default="HEAD",
+    help="Git ref to diff against for --incremental (default: HEAD)",
+)
+@click.option(
+    "--force",
+    is_flag=True,
+    default=False,
+    help="Discard the existing index first (required after switching "
+    "embedding models)",
+)
+def search_index(path: str, incremental: bool, ref: str, force: bool) -> None:
+    """Index a codebase for semantic search."""
+    from .memory.vector_search import VectorSearch
+
+    try:
+        vector = VectorSearch(
+            persist_directory=_default_data_dir() / "vectors",
+            progress_callback=_download_progress,
+            allow_mismatch=force,
+        )
+    except RuntimeError as e:
+        raise click.ClickException(str(e)) from e
+    if force:
+        vector.clear()
+
+    if incremental:
+        stats = asyncio.run(vector.index_changed(Path(path), ref=ref))
+        if stats is not None:
+            click.echo(
+                f"Reindexed {stats['files_indexed']} changed files "
+                f"({stats['files_purged']} purged, {stats['errors']} errors)"
+            )
+            return
+        click.echo("Not a git repository; running a full index", err=True)
+
+    click.echo(f"Indexing {path}...", err=True)
+    stats = asyncio.run(vector.index_codebase(Path(path)))
+    click.echo(
+        f"Indexed {stats['files_indexed']} files "
+        f"({stats['snippets_indexed']} snippets, {stats['errors']} errors)"
+    )
+
+
+@search.command("reindex")
+@click.argument("path", type=click.Path(exists=True, path_type=Path))
+def search_reindex(path: Path) -> None:
+    """Re-chunk and re-embed one file or directory immediately."""
+    from .memory.vector_search import VectorSearch
+
+    try:
+        vector = VectorSearch(
+            persist_directory=_default_data_dir() / "vectors",
+            progress_callback=_download_progress,
+        )
+    except RuntimeError as e:
+        raise click.ClickException(str(e)) from e
+
+    stats = asyncio.run(vector.reindex_path(path))
+    click.echo(
+        f"Reindexed {stats['files_indexed']} files ({stats['errors']} errors)"
+    )
+
+
+@search.command("status")
+@click.option(
+    "--files",
+    "show_files",
+    is_flag=True,
+    default=False,
+    help="List indexed files with chunk counts and freshness",
+)
+def search_status(show_files: bool) -> None:
+    """Show index statistics and per-file freshness."""
+    from .memory.vector_search import VectorSearch
+
+    try:
+        vector = VectorSearch(persist_directory=_default_data_dir() / "vectors")
+    except RuntimeError as e:
+        raise click.ClickException(str(e)) from e
+    report = vector.index_status()
+
+    stale = [entry for entry in report if entry["stale"]]
+    click.echo(
+        f"{len(report)} files indexed ({vector.count()} chunks, "
+        f"{len(stale)} stale)"
+    )
+
+    if not show_files:
+        return
+
+    for entry in report:
+        if entry["missing"]:
+            flag = "missing"
+        elif entry["stale"]:
+            flag = "stale"
+        else:
+            flag = "fresh"
+        click.echo(f"{entry['file_path']}  {entry['chunks']} chunks  [{flag}]")
+
+
+@search.command("query")
+@click.argument("query")
+@click.option("--limit", default=10, help="Maximum number of results")
+@click.option("--language", default=None, help="Filter by language (python, rust, ...)")
+@click.option(
+    "--page-size",
+    default=0,
+    help="Results per page for interactive paging (0 = all at once)",
+)
+@click.option(
+    "--prefer-documented",
+    is_flag=True,
+    default=False,
+    help="Boost documented chunks for conceptual queries",
+)
+@click.option(
+    "--rerank",
+    is_flag=True,
+    default=False,
+    help="Rerank results with the configured LLM (slower, costs tokens)",
+)
+@click.option(
+    "--context",
+    default=0,
+    help="Show this many source lines around each result (0 = none)",
+)
+def search_query(
+    query: str,
+    limit: int,
+    language: str | None,
+    page_size: int,
+    prefer_documented: bool,
+    rerank: bool,
+    context: int,
+) -> None:
+    """Search the index for semantically similar code."""
+    from .memory.vector_search import VectorSearch
+
+    try:
+        vector = VectorSearch(
+            persist_directory=_default_data_dir() / "vectors",
+            progress_callback=_download_progress,
+        )
+    except RuntimeError as e:
+        raise click.ClickException(str(e)) from e
+    results = vector.search(
+        query,
+        n_results=limit,
+        filter_language=language,
+        prefer_documented=prefer_documented,
+    )
+
+    if rerank and results:
+        from .config import get_settings
+        from .models import ModelRouter
+
+        model_name = get_settings().default_model
+        click.echo(
+            f"Reranking {len(results)} results with {model_name} "
+            "(sends snippets to the LLM, costs tokens)...",
+            err=True,
+        )
+        router = ModelRouter(default_model=model_name)
+        llm = router.get_model(model_name=model_name, temperature=0.0)
+        results = asyncio.run(vector.rerank(query, results, llm))
+
+    if context > 0:
+        attach_search_context(results, context)
+    search_display(results, page_size=page_size)
+
+
+def attach_search_context(results: list[dict[str, Any]], context: int) -> None:
+    """Read surrounding source lines into each result in place.
+
+    Grounds chunks in their file without opening it: context lines land
+    in context_before/context_after. Files that moved or shrank since
+    indexing get a context_note instead of a failed search.
+    """
+    for result in results:
+        metadata = result.get("metadata", {})
+        path = metadata.get("file_path")
+        start = metadata.get("start_line")
+        end = metadata.get("end_line")
+        if not path or not isinstance(start, int) or not isinstance(end, int):
+            result["context_note"] = "source unavailable"
+            continue
+        try:
+            lines = Path(path).read_text(errors="replace").splitlines()
+        except OSError:
+            result["context_note"] = "source unavailable"
+            continue
+        if start > len(lines):
+            result["context_note"] = "source changed since indexing"
+            continue
+        # start_line/end_line are 1-based and inclusive
+        result["context_before"] = lines[max(start - 1 - context, 0) : start - 1]
+        result["context_after"] = lines[end : end + context]
+
+
+def search_display(results: list[dict[str, Any]], page_size: int = 0) -> None:
+    """Render search results, with interactive paging on a TTY.
+
+    When page_size is set and stdout is a TTY, results are shown one page at
+    a time (space/enter for next page, q to stop). Piped output always gets
+    everything at once so scripts see the full result set.
+    """
+    total = len(results)
+    if total == 0:
+        click.echo("No results found")
+        return
+
+    interactive = page_size > 0 and sys.stdout.isatty()
+    if not interactive:
+        for result in results:
+            _print_search_result(result)
+        click.echo(f"\n{total} results")
+        return
+
+    total_pages = math.ceil(total / page_size)
+    for page in range(total_pages):
+        for result in results[page * page_size : (page + 1) * page_size]:
+            _print_search_result(result)
+
+        click.echo(f"-- page {page + 1} of {total_pages} ({total} results) --")
+
+        if page < total_pages - 1:
+            click.echo("[space/enter: next page, q: quit] ", nl=False)
+            char = click.getchar()
+            click.echo()
+            if char.lower() == "q":
+                return
+
+
+def _print_search_result(result: dict[str, Any]) -> None:
+    """Print a single search result."""
+    metadata = result.get("metadata", {})
+    location = (
+        f"{metadata.get('file_path', '?')}:"
+        f"{metadata.get('start_line', '?')}-{metadata.get('end_line', '?')}"
+    )
+    doc_boost = result.get("doc_boost")
+    boost_note = f", +doc {doc_boost:.2f}" if doc_boost else ""
+    rerank_score = result.get("rerank_score")
+    if rerank_score is not None:
+        boost_note += f", rerank {rerank_score:.1f}"
+    symbol = metadata.get("symbol")
+    symbol_note = f" in {symbol}" if symbol else ""
+    click.echo(
+        f"\n{location}{symbol_note} "
+        f"({metadata.get('language', 'unknown')}, "
+        f"sim {result.get('similarity', 0.0):.2f}{boost_note})"
+    )
+    for line in result.get("context_before", []):
+        click.echo(f"  | {line}")
+    click.echo(result.get("content", ""))
+    for line in result.get("context_after", []):
+        click.echo(f"  | {line}")
+    if result.get("context_note"):
+        click.echo(f"  [{result['context_note']}]")
+
+
+@main.group()
+def permissions() -> None:
+    """Manage approved command patterns."""
+    pass
+
+
+@permissions.command("list")
+def permissions_list() -> None:
+    """List approved command patterns."""
+    from .permissions import PermissionsManager
+
+    manager = PermissionsManager()
+    rules = manager.list_rules()
+    if not rules:
+        click.echo("No approved command patterns")
+        return
+    for rule in rules:
+        click.echo(f"{rule.pattern}  ({rule.scope})")
+
+
+@permissions.command("remove")
+@click.argument("pattern")
+def permissions_remove(pattern: str) -> None:
+    """Remove an approved command pattern."""
+    from .permissions import PermissionsManager
+
+    manager = PermissionsManager()
+    if manager.remove(pattern):
+        click.echo(f"Removed: {pattern}")
+    else:
+        raise click.ClickException(f"No rule matching pattern: {pattern}")
+
+
+@main.group()
+def auth() -> None:
+    """Manage provider credentials."""
+    pass
+
+
+@auth.command("import")
+@click.option(
+    "--test",
+    "test_keys",
+    is_flag=True,
+    default=False,
+    help="Verify each key with a minimal request before storing",
+)
+def auth_import(test_keys: bool) -> None:
+    """Import API keys from the environment into the credential store.
+
+    Scans known environment variables (OPENAI_API_KEY, ANTHROPIC_API_KEY,
+    ...) and a local .env file. Key values are never printed or logged.
+    """
+    from .auth import AuthStore, PROVIDER_ENV_VARS, mask_key
+
+    store = AuthStore()
+    found = store.discover()
+    if not found:
+        names = ", ".join(PROVIDER_ENV_VARS.values())
+        click.echo(f"No provider keys found (looked for: {names})")
+        return
+
+    imported = []
+    for provider in sorted(found):
+        key = found[provider]
+        if test_keys and not _test_provider_key(provider, key):
+            click.echo(f"{provider}: key {mask_key(key)} failed validation, skipped")
+            continue
+        store.set_key(provider, key)
+        imported.append(provider)
+
+    if not imported:
+        raise click.ClickException("No keys passed validation")
+
+    store.save()
+    for provider in imported:
+        click.echo(f"{provider}: configured ({mask_key(found[provider])})")
+    click.echo(f"Stored credentials for {len(imported)} provider(s)")
+
+
+@auth.command("list")
+def auth_list() -> None:
+    """List providers with stored credentials."""
+    from .auth import AuthStore, mask_key
+
+    store = AuthStore()
+    providers = store.providers()
+    if not providers:
+        click.echo("No stored credentials (run: aircher auth import)")
+        return
+    for provider in providers:
+        click.echo(f"{provider}  ({mask_key(store.get_key(provider) or '')})")
+
+
+@main.group()
+def config() -> None:
+    """Inspect configuration."""
+    pass
+
+
+@config.command("effective")
+def config_effective() -> None:
+    """Show the resolved configuration after merging all layers."""
+    from .config import effective_config
+
+    for label, value in effective_config():
+        click.echo(f"{label}: {value}")
+
+
+@main.group()
+def model() -> None:
+    """Inspect supported models."""
+    pass
+
+
+@model.command("list")
+@click.option(
+    "--sort",
+    "sort_key",
+    type=click.Choice(["name", "context", "price"]),
+    default="name",
+    help="Sort order within each provider group (default: name)",
+)
+@click.option("--limit", default=0, help="Maximum models to show (0 = all)")
+def model_list(sort_key: str, limit: int) -> None:
+    """List supported models grouped by provider."""
+    from .models import SUPPORTED_MODELS
+
+    sort_keys = {
+        "name": lambda item: item[0],
+        # Largest context and cheapest input first - that's what you scan for
+        "context": lambda item: -item[1].context_window,
+        "price": lambda item: item[1].cost_per_1k_input,
+    }
+    models = sorted(SUPPORTED_MODELS.items(), key=sort_keys[sort_key])
+    if limit > 0:
+        models = models[:limit]
+
+    by_provider: dict[str, list[tuple[str, Any]]] = {}
+    for name, config in models:
+        by_provider.setdefault(config.provider.value, []).append((name, config))
+
+    for index, (provider, entries) in enumerate(sorted(by_provider.items())):
+        if index:
+            click.echo()
+        click.echo(f"{provider}:")
+        for name, config in entries:
+            if config.cost_per_1k_input == 0 and config.cost_per_1k_output == 0:
+                price = "free"
+            else:
+                price = (
+                    f"${config.cost_per_1k_input * 1000:.2f} in / "
+                    f"${config.cost_per_1k_output * 1000:.2f} out per 1M"
+                )
+            click.echo(
+                f"  {name}  ({config.tier.value}, "
+                f"ctx {config.context_window // 1000}k, {price})"
+            )
+
+
+@main.command("export-context")
+@click.argument("message")
+@click.option(
+    "--model",
+    default="gpt-4o-mini",
+    help="Model to assemble for (affects system/developer role split)",
+)
+@click.option(
+    "--context",
+    "context_files",
+    default=None,
+    help="Comma-separated files to inject as context",
+)
+@click.option(
+    "--system",
+    "system_prompt",
+    default=None,
+    help="Override the default system prompt",
+)
+@click.option(
+    "--json",
+    "as_json",
+    is_flag=True,
+    default=False,
+    help="Emit the context as JSON structured by role",
+)
+def export_context(
+    message: str,
+    model: str,
+    context_files: str | None,
+    system_prompt: str | None,
+    as_json: bool,
+) -> None:
+    """Print the context a message would send, without calling a provider.
+
+    Shows the assembled system prompt (instructions file, git state,
+    project notes) and the final user message, so prompt changes can be
+    inspected and iterated on without spending tokens.
+    """
+    from .agent import AircherAgent
+
+    if context_files:
+        message = _inject_context_files(message, context_files)
+
+    agent = AircherAgent(model_name=model, enable_memory=False)
+    entries = agent.export_context(message, system_prompt_override=system_prompt)
+
+    if as_json:
+        click.echo(json_module.dumps(entries, indent=2))
+        return
+    for entry in entries:
+        click.echo(f"--- {entry['role']} ---")
+        click.echo(entry["content"])
+        click.echo()
+
+
+def _test_provider_key(provider: str, key: str) -> bool:
+    """Check a key with a minimal live request. Never logs the key."""
+    try:
+        if provider == "openai":
+            from langchain_openai import ChatOpenAI
+
+            ChatOpenAI(model="gpt-4o-mini", api_key=key, max_tokens=1).invoke("ping")
+        elif provider == "anthropic":
+            from langchain_anthropic import ChatAnthropic
+
+            ChatAnthropic(
+                model="claude-3-5-haiku-latest", api_key=key, max_tokens=1
+            ).invoke("ping")
+        elif provider == "openrouter":
+            from langchain_openai import ChatOpenAI
+
+            ChatOpenAI(
+                model="openai/gpt-4o-mini",
+                api_key=key,
+                base_url="https://openrouter.ai/api/v1",
+                max_tokens=1,
+            ).invoke("ping")
+        else:
+            return True
+        return True
+    except Exception:
+        return False
+
+
+@main.group(invoke_without_command=True)
+@click.option("--tag", default=None, help="Only show sessions with this tag")
+@click.option("--limit", default=20, help="Maximum sessions to show (default: 20)")
+@click.pass_context
+def sessions(ctx: click.Context, tag: str | None, limit: int) -> None:
+    """List and manage stored sessions (lists by default, most recent first).
+
+    Example:
+        aircher sessions --tag refactor
+    """
+    if ctx.invoked_subcommand is not None:
+        return
+
+    from .sessions import SessionStorage
+
+    storage = SessionStorage()
+    found = storage.list_sessions(limit=limit, tag=tag)
+    if not found:
+        click.echo("No matching sessions" if tag else "No sessions")
+        return
+    for session in found:
+        title = session.metadata.get("title", "")
+        tags = session.metadata.get("tags", [])
+        line = f"{session.id}  {session.last_activity:%Y-%m-%d %H:%M}"
+        if title:
+            line += f"  {title}"
+        if tags:
+            line += f"  [{', '.join(tags)}]"
+        click.echo(line)
+
+
+@sessions.command("export")
+@click.argument("session_id")
+@click.option(
+    "--output",
+    "-o",
+    type=click.Path(path_type=Path),
+    default=None,
+    help="Write the export to a file instead of stdout",
+)
+def sessions_export(session_id: str, output: Path | None) -> None:
+    """Export a session transcript as JSON (replayable via sessions replay)."""
+    from datetime import datetime
+
+    from .sessions import SessionStorage
+
+    storage = SessionStorage()
+    if storage.get_session(session_id) is None:
+        raise click.ClickException(f"No session: {session_id}")
+
+    payload = {
+        "schema": "aircher-session/1",
+        "session_id": session_id,
+        "exported_at": datetime.now().isoformat(),
+        "messages": storage.get_messages(session_id, limit=10000),
+    }
+    text = json_module.dumps(payload, indent=2, default=str)
+    if output is not None:
+        try:
+            output.write_text(text + "\n")
+        except OSError as e:
+            raise click.ClickException(f"Failed to write {output}: {e}") from e
+        click.echo(f"Exported {len(payload['messages'])} messages to {output}")
+    else:
+        click.echo(text)
+
+
+@sessions.command("replay")
+@click.argument("file", type=click.Path(exists=True, path_type=Path))
+@click.option(
+    "--model", default="gpt-4o-mini", help="Model to replay the turns against"
+)
+@click.option(
+    "--dry-run",
+    is_flag=True,
+    default=False,
+    help="Print the user turns without sending them",
+)
+def sessions_replay(file: Path, model: str, dry_run: bool) -> None:
+    """Re-run an exported transcript's user turns against a model.
+
+    Each user turn is sent in order with the accumulated fresh responses
+    as history - useful for regression-testing prompts on a new model.
+    """
+    try:
+        payload = json_module.loads(file.read_text())
+    except (OSError, ValueError) as e:
+        raise click.ClickException(f"Not a readable session export: {e}") from e
+
+    turns = [
+        m.get("content", "")
+        for m in payload.get("messages", [])
+        if m.get("role") == "user" and m.get("content")
+    ]
+    if not turns:
+        raise click.ClickException("Export contains no user turns")
+
+    if dry_run:
+        for i, turn in enumerate(turns, 1):
+            click.echo(f"[{i}] {turn}")
+        return
+
+    from .agent import AircherAgent
+
+    agent = AircherAgent(model_name=model, enable_memory=False)
+    history: list[dict[str, str]] = []
+
+    async def _replay() -> None:
+        for i, turn in enumerate(turns, 1):
+            click.echo(f"[{i}/{len(turns)}] > {turn}")
+            result = await agent.run(message=turn, history=history or None)
+            response = result.get("response", "")
+            click.echo(response)
+            click.echo("")
+            history.append({"role": "user", "content": turn})
+            history.append({"role": "assistant", "content": response})
+
+    asyncio.run(_replay())
+
+
+def _maybe_run_onboarding(ctx: click.Context) -> str | None:
+    """First-run setup: provider key, default model, optional indexing.
+
+    Runs at most once (a marker file records the offer, even when
+    skipped) and only on an interactive terminal with no keys
+    configured anywhere. Returns a model to use for this session, or
+    None to keep the caller's default.
+    """
+    from .auth import PROVIDER_ENV_VARS, AuthStore, mask_key
+    from .models import SUPPORTED_MODELS
+
+    marker = Path.home() / ".aircher" / "onboarded"
+    if marker.exists() or not sys.stdin.isatty():
+        return None
+
+    store = AuthStore()
+    if store.providers() or any(
+        os.environ.get(var) for var in PROVIDER_ENV_VARS.values()
+    ):
+        return None
+
+    marker.parent.mkdir(parents=True, exist_ok=True)
+    marker.touch()
+
+    click.echo("Welcome to Aircher - no provider API keys are configured yet.")
+    if not click.confirm("Set one up now?", default=True):
+        click.echo("Skipping setup - run 'aircher auth import' when ready.")
+        return None
+
+    # .env files in the project may already hold keys worth importing
+    discovered = store.discover(env=os.environ)
+    if discovered and click.confirm(
+        f"Found keys for {', '.join(sorted(discovered))} in .env - import them?",
+        default=True,
+    ):
+        for provider, key in discovered.items():
+            store.set_key(provider, key)
+        store.save()
+
+    if not store.providers():
+        provider = click.prompt(
+            "Provider",
+            type=click.Choice(sorted(PROVIDER_ENV_VARS)),
+            default="anthropic",
+        )
+        key = click.prompt(f"{provider} API key", hide_input=True).strip()
+        if not key:
+            click.echo("No key entered - run 'aircher auth import' when ready.")
+            return None
+        store.set_key(provider, key)
+        store.save()
+        click.echo(f"{provider}: configured ({mask_key(key)})")
+
+    store.apply_to_environment()
+
+    provider = sorted(store.providers())[0]
+    provider_models = sorted(
+        name
+        for name, config in SUPPORTED_MODELS.items()
+        if config.provider.value == provider
+    )
+    model = click.prompt(
+        "Default model",
+        type=click.Choice(provider_models),
+        default=provider_models[0],
+    )
+    click.echo(
+        f"Add AIRCHER_DEFAULT_MODEL={model} to .env to keep this default."
+    )
+
+    if click.confirm(
+        "Index this project for semantic search now?", default=False
+    ):
+        ctx.invoke(search_index, path=".", incremental=False, ref="HEAD", force=False)
+
+    return model
+
+
+@main.command()
+@click.option(
+    "--model",
+    default="gpt-4o-mini",
+    help="LLM model to use (default: gpt-4o-mini)",
+)
+@click.option(
+    "--enable-memory/--no-memory",
+    default=True,
+    help="Enable/disable memory systems (default: enabled)",
+)
+@click.option(
+    "--resume",
+    is_flag=True,
+    default=False,
+    help="Resume the most recent session for this project",
+)
+@click.pass_context
+def tui(ctx: click.Context, model: str, enable_memory: bool, resume: bool) -> None:
+    """Run the interactive terminal UI.
+
+    Example:
+        aircher tui --model gpt-4o --resume
+    """
+    from .tui import TuiManager
+
+    onboarding_model = _maybe_run_onboarding(ctx)
+    if onboarding_model:
+        model = onboarding_model
+
+    manager = TuiManager(model_name=model, enable_memory=enable_memory, resume=resume)
+
+    try:
+        asyncio.run(manager.run())
+    except KeyboardInterrupt:
+        pass
+
+
+@main.command()
+@click.option(
+    "--model",
+    default="gpt-4o-mini",
+    help="LLM model to use (default: gpt-4o-mini)",
+)
+@click.option(
+    "--enable-memory/--no-memory",
+    default=True,
+    help="Enable/disable memory systems (default: enabled)",
+)
+@click.pass_context
+def serve(ctx: click.Context, model: str, enable_memory: bool) -> None:
     """Run Aircher as an ACP server (JSON-RPC over stdio).
 
     This mode allows Aircher to be used by ACP-compatible editors like Zed.
@@ -41,8 +1160,13 @@ def serve(model: str, enable_memory: bool) -> None:
     Example:
         aircher serve --model gpt-4o
     """
-    click.echo(f"Starting Aircher ACP server with {model}...")
-    click.echo(f"Memory systems: {'enabled' if enable_memory else 'disabled'}")
+    quiet = ctx.obj.get("quiet", False)
+
+    if not quiet:
+        click.echo(f"Starting Aircher ACP server with {model}...", err=True)
+        click.echo(
+            f"Memory systems: {'enabled' if enable_memory else 'disabled'}", err=True
+        )
 
     from .agent import AircherAgent
     from .protocol.server import ACPServer
@@ -56,7 +1180,8 @@ def serve(model: str, enable_memory: bool) -> None:
     try:
         asyncio.run(server.start())
     except KeyboardInterrupt:
-        click.echo("\nShutting down ACP server...")
+        if not quiet:
+            click.echo("\nShutting down ACP server...", err=True)
         server.stop()
 
 
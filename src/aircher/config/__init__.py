@@ -1,12 +1,108 @@
-"""Configuration management for Aircher."""
+"""Configuration management for Aircher.
 
+Every setting can be overridden with an ``AIRCHER_*`` environment
+variable, applied above ``.env`` file values (explicit CLI flags still
+win). The mapping convention is the upper-cased field name behind the
+``AIRCHER_`` prefix, with ``__`` as the delimiter into nested models:
+
+    AIRCHER_DEFAULT_MODEL=gpt-4o        # Settings.default_model
+    AIRCHER_SESSION_COST_BUDGET=2.50    # Settings.session_cost_budget
+    AIRCHER_UI__STATUS_STYLE=plain      # Settings.ui.status_style
+
+Values are parsed and validated against the field's declared type at
+load time (dict and list fields take JSON), so a typo'd value fails
+loudly instead of being silently ignored - the intended deployment
+story for containers and CI where editing files is awkward.
+"""
+
+import os
+import re
 from functools import lru_cache
 from pathlib import Path
 from typing import Optional
 
-from pydantic import Field
+from pydantic import BaseModel, Field, field_validator
 from pydantic_settings import BaseSettings, SettingsConfigDict
 
+# RFC 7230 header-name token characters
+_HEADER_NAME_RE = re.compile(r"^[!#$%&'*+\-.^_`|~0-9A-Za-z]+$")
+
+
+class UISettings(BaseModel):
+    """Terminal UI settings."""
+
+    autosave_interval: int = Field(
+        default=30,
+        description="Seconds between TUI UI-state autosaves (0 disables)",
+    )
+    status_style: str = Field(
+        default="fun",
+        description="Streaming status style: fun (themed), plain, or minimal",
+    )
+    handle_suspend: bool = Field(
+        default=True,
+        description="Repaint a status hint when resumed after Ctrl+Z "
+        "(disable if it conflicts with other signal handling)",
+    )
+    confirm_quit_with_draft: bool = Field(
+        default=True,
+        description="Require a second /quit when an unsent draft or queued "
+        "message would be lost",
+    )
+    clear_confirm_threshold: int = Field(
+        default=5,
+        description="Require a second /clear when the conversation has "
+        "more than this many messages (0 disables confirmation)",
+    )
+    show_welcome: bool = Field(
+        default=True,
+        description="Show the welcome banner on TUI startup "
+        "(/welcome off dismisses it per project)",
+    )
+    max_message_width: int = Field(
+        default=100,
+        description="Column cap for chat message panels on wide terminals "
+        "(a reading-width limit, like web UIs use)",
+    )
+    diff_palette: str = Field(
+        default="default",
+        description="Diff colors: default (red/green) or colorblind (blue/orange)",
+    )
+
+
+class ContextBudgetSettings(BaseModel):
+    """Context window budget allocation.
+
+    Fractions of the window reserved per content category; normalized at
+    use so they don't have to sum to exactly 1.0.
+    """
+
+    instructions_fraction: float = Field(
+        default=0.15,
+        description="Window share for system prompt and task state",
+    )
+    retrieval_fraction: float = Field(
+        default=0.35,
+        description="Window share for retrieved files and tool results",
+    )
+    history_fraction: float = Field(
+        default=0.5,
+        description="Window share for conversation history",
+    )
+
+
+class SecuritySettings(BaseModel):
+    """Security-related settings."""
+
+    secret_scan_enabled: bool = Field(
+        default=True,
+        description="Scan outgoing messages for secrets and warn before sending",
+    )
+    secret_patterns: dict[str, str] = Field(
+        default_factory=dict,
+        description="Additional named regex patterns to treat as secrets",
+    )
+
 
 class Settings(BaseSettings):
     """Application settings."""
@@ -15,14 +111,146 @@ class Settings(BaseSettings):
         env_file=".env",
         env_file_encoding="utf-8",
         env_prefix="AIRCHER_",
+        env_nested_delimiter="__",
+    )
+
+    # Terminal UI
+    ui: UISettings = Field(default_factory=UISettings)
+
+    # Security
+    security: SecuritySettings = Field(default_factory=SecuritySettings)
+
+    # Context window budgets
+    context_budget: ContextBudgetSettings = Field(
+        default_factory=ContextBudgetSettings
     )
 
     # Agent Configuration
     agent_mode: str = Field(default="read", description="Agent mode: read/edit/turbo")
     bypass_safety: bool = Field(default=False, description="Bypass safety checks")
+    tool_allowlist: list[str] = Field(
+        default_factory=list,
+        description="Only these tools are available to the agent (empty = all)",
+    )
+    tool_denylist: list[str] = Field(
+        default_factory=list,
+        description="These tools are never available to the agent",
+    )
+    tool_concurrency: int = Field(
+        default=1,
+        description="Max read-only tool calls executed concurrently "
+        "(1 = sequential; writes and commands always run serially)",
+    )
+    web_search_enabled: bool = Field(
+        default=False,
+        description="Register the web_search tool (needs BRAVE_SEARCH_API_KEY; "
+        "only modes with network access can use it)",
+    )
+    max_history_messages: int = Field(
+        default=40,
+        description="Most recent conversation messages sent per request "
+        "(display history is unaffected; 0 disables the cap)",
+    )
+    system_prompt_token_budget: int = Field(
+        default=2000,
+        description="Estimated-token cap for the assembled system prompt; "
+        "lower-priority sections (git state, project notes) are dropped "
+        "first (0 disables)",
+    )
+    resume_compact_tokens: int = Field(
+        default=20000,
+        description="Auto-window resumed sessions whose history exceeds this "
+        "many estimated tokens, keeping the request small until /compact "
+        "(display history stays full; 0 disables)",
+    )
+    auto_index_on_start: bool = Field(
+        default=False,
+        description="Build/refresh the semantic search index in the "
+        "background when the TUI starts (incremental when possible)",
+    )
+    related_session_context: int = Field(
+        default=0,
+        description="Inject brief summaries of up to this many related past "
+        "sessions for the project with the first message of a session "
+        "(0 disables)",
+    )
+    auto_title_sessions: bool = Field(
+        default=False,
+        description="Generate a short session title from the first exchange "
+        "using the summarization utility model",
+    )
 
     # Model Configuration
     default_model: str = Field(default="gpt-4o-mini", description="Default LLM model")
+    temperature: float = Field(
+        default=0.7,
+        ge=0.0,
+        le=2.0,
+        description="Sampling temperature for chat requests",
+    )
+    reasoning_effort: str | None = Field(
+        default=None,
+        description="low/medium/high thinking budget for reasoning models "
+        "(/effort overrides per session; ignored by other models)",
+    )
+    prompt_caching: bool = Field(
+        default=True,
+        description="Mark large stable system prompts as cacheable on "
+        "providers that take explicit cache-control hints",
+    )
+    slow_request_seconds: float = Field(
+        default=30.0,
+        description="Requests slower than this count toward a model "
+        "downgrade suggestion (0 disables latency tracking)",
+    )
+    slow_request_threshold: int = Field(
+        default=3,
+        description="Consecutive slow/failed requests before suggesting "
+        "a faster model",
+    )
+    auto_downgrade: bool = Field(
+        default=False,
+        description="Switch to the suggested faster model automatically "
+        "instead of only suggesting it",
+    )
+    model_downgrades: dict[str, str] = Field(
+        default_factory=dict,
+        description="Explicit slow-model to faster-model mapping, "
+        "consulted before the tier-based default",
+    )
+    session_cost_budget: float = Field(
+        default=0.0,
+        description="USD budget per session; streamed responses warn at "
+        "80% and 100% as estimated cost accrues (0 disables)",
+    )
+    budget_hard_stop: bool = Field(
+        default=False,
+        description="Stop a streaming response at the session budget "
+        "instead of only warning",
+    )
+    budget_behavior: str = Field(
+        default="block",
+        description="With budget_hard_stop, what an over-budget send does: "
+        "block refuses until config changes, prompt asks whether to raise "
+        "the budget for this session and continue",
+    )
+    provider_headers: dict[str, dict[str, str]] = Field(
+        default_factory=dict,
+        description="Extra HTTP headers per provider for proxies/gateways "
+        '(e.g. {"openai": {"OpenAI-Organization": "org-..."}}); values are '
+        "treated as sensitive and never logged",
+    )
+    task_models: dict[str, str] = Field(
+        default_factory=dict,
+        description="Explicit task-category to model mapping (e.g. "
+        "summarization=gpt-4o-mini, code_generation=claude-sonnet-4-20250514); "
+        "wins over utility_model and tier heuristics",
+    )
+    utility_model: str | None = Field(
+        default=None,
+        description="Cheaper model for auxiliary tasks (summarization, "
+        "context analysis); falls back to the chat model when unset",
+    )
     openai_api_key: str | None = Field(default=None, description="OpenAI API key")
     anthropic_api_key: str | None = Field(default=None, description="Anthropic API key")
     vllm_base_url: str = Field(
@@ -43,8 +271,67 @@ class Settings(BaseSettings):
     debug: bool = Field(default=False, description="Enable debug logging")
     log_level: str = Field(default="INFO", description="Log level")
 
+    @field_validator("provider_headers")
+    @classmethod
+    def _validate_header_names(
+        cls, value: dict[str, dict[str, str]]
+    ) -> dict[str, dict[str, str]]:
+        """Reject malformed header names at load time, not mid-request."""
+        for provider, headers in value.items():
+            for name in headers:
+                if not _HEADER_NAME_RE.match(name):
+                    raise ValueError(
+                        f"Invalid header name {name!r} for provider {provider}"
+                    )
+        return value
+
 
 @lru_cache
 def get_settings() -> Settings:
     """Get cached settings instance."""
     return Settings()
+
+
+def effective_config(
+    model: str | None = None,
+    mode: str | None = None,
+    project_dir: Path | None = None,
+) -> list[tuple[str, str]]:
+    """Resolved configuration as (label, value) pairs for status displays.
+
+    Merges settings (env/.env over defaults) with stored credentials and
+    any live overrides the caller passes. Only provider names appear for
+    authentication - key values never do. Backs /whoami in the TUI and
+    `aircher config effective` in the CLI.
+    """
+    # Lazy imports: models imports config, so top-level would cycle
+    from ..auth import PROVIDER_ENV_VARS, AuthStore
+    from ..models import SUPPORTED_MODELS
+
+    settings = get_settings()
+    model = model or settings.default_model
+    model_config = SUPPORTED_MODELS.get(model)
+    provider = model_config.provider.value if model_config else "unknown"
+
+    authenticated = sorted(
+        set(AuthStore().providers())
+        | {p for p, var in PROVIDER_ENV_VARS.items() if os.environ.get(var)}
+    )
+
+    if settings.session_cost_budget > 0:
+        budget = f"${settings.session_cost_budget:.2f}"
+        if settings.budget_hard_stop:
+            budget += " (hard stop)"
+    else:
+        budget = "none"
+
+    return [
+        ("project", str((project_dir or Path.cwd()).resolve())),
+        ("model", model),
+        ("provider", provider),
+        ("mode", mode or settings.agent_mode),
+        # The agent's actual default, not settings.data_dir (unused there)
+        ("data dir", str(Path.home() / ".aircher" / "data")),
+        ("session budget", budget),
+        ("authenticated providers", ", ".join(authenticated) or "none"),
+    ]
@@ -2,6 +2,7 @@
 
 from datetime import datetime, timedelta
 from enum import Enum
+from pathlib import Path
 from typing import Any
 from uuid import UUID, uuid4
 
@@ -12,6 +13,62 @@ from pydantic import BaseModel, Field
 from ..memory.integration import MemoryIntegration
 
 
+# Default token budget for a single file read injected into context
+DEFAULT_READ_TOKEN_BUDGET = 8000
+
+
+def read_for_context(path: Path | str, token_budget: int = DEFAULT_READ_TOKEN_BUDGET) -> tuple[str, bool]:
+    """Read a file for LLM context, eliding the middle if it exceeds budget.
+
+    Files within budget are returned whole. Oversized files keep the head
+    (~2/3 of the budget) and tail (~1/3) with an elision marker in between,
+    so the agent sees imports/signatures and trailing definitions instead
+    of blowing the context window on a single read.
+
+    Args:
+        path: File to read.
+        token_budget: Approximate token allowance (1 token ~ 4 chars).
+
+    Returns:
+        The (possibly elided) content, and whether elision happened.
+    """
+    lines = Path(path).read_text(encoding="utf-8", errors="replace").splitlines()
+
+    def line_tokens(line: str) -> int:
+        return max(1, len(line) // 4)
+
+    total_tokens = sum(line_tokens(line) for line in lines)
+    if total_tokens <= token_budget:
+        return "\n".join(lines), False
+
+    head_budget = token_budget * 2 // 3
+    tail_budget = token_budget - head_budget
+
+    head: list[str] = []
+    used = 0
+    for line in lines:
+        used += line_tokens(line)
+        if used > head_budget:
+            break
+        head.append(line)
+
+    tail: list[str] = []
+    used = 0
+    for line in reversed(lines):
+        used += line_tokens(line)
+        if used > tail_budget:
+            break
+        tail.append(line)
+    tail.reverse()
+
+    elided = len(lines) - len(head) - len(tail)
+    marker = (
+        f"... [{elided} lines elided: file exceeds {token_budget}-token "
+        f"context budget, use offset/limit reads for the middle] ..."
+    )
+    return "\n".join([*head, marker, *tail]), True
+
+
 class ContextItemType(str, Enum):
     """Types of context items."""
 
@@ -24,6 +81,59 @@ class ContextItemType(str, Enum):
     KNOWLEDGE_GRAPH_QUERY = "knowledge_graph_query"
 
 
+class ContextCategory(str, Enum):
+    """Budget categories for context allocation."""
+
+    INSTRUCTIONS = "instructions"
+    RETRIEVAL = "retrieval"
+    HISTORY = "history"
+
+
+# Which budget category each item type counts against
+CATEGORY_FOR_TYPE = {
+    ContextItemType.SYSTEM_PROMPT: ContextCategory.INSTRUCTIONS,
+    ContextItemType.TASK_STATE: ContextCategory.INSTRUCTIONS,
+    ContextItemType.USER_MESSAGE: ContextCategory.HISTORY,
+    ContextItemType.ASSISTANT_RESPONSE: ContextCategory.HISTORY,
+    ContextItemType.TOOL_RESULT: ContextCategory.RETRIEVAL,
+    ContextItemType.CODE_SNIPPET: ContextCategory.RETRIEVAL,
+    ContextItemType.KNOWLEDGE_GRAPH_QUERY: ContextCategory.RETRIEVAL,
+}
+
+
+class ContextBudget(BaseModel):
+    """Fractional window allocation between content categories.
+
+    Fractions are normalized, so partial overrides (e.g. bumping only
+    retrieval_fraction) still produce a full allocation.
+    """
+
+    instructions_fraction: float = 0.15
+    retrieval_fraction: float = 0.35
+    history_fraction: float = 0.5
+
+    def allocate(self, token_limit: int) -> dict[ContextCategory, int]:
+        """Token allowance per category for a given window size."""
+        total = (
+            self.instructions_fraction
+            + self.retrieval_fraction
+            + self.history_fraction
+        )
+        if total <= 0:
+            total = 1.0
+        return {
+            ContextCategory.INSTRUCTIONS: int(
+                token_limit * self.instructions_fraction / total
+            ),
+            ContextCategory.RETRIEVAL: int(
+                token_limit * self.retrieval_fraction / total
+            ),
+            ContextCategory.HISTORY: int(
+                token_limit * self.history_fraction / total
+            ),
+        }
+
+
 class ContextItem(BaseModel):
     """A single item in the context window."""
 
@@ -54,6 +164,7 @@ class ContextWindow(BaseModel):
     token_limit: int = 120000  # ~80% of 150k context window
     pruning_threshold: float = 0.8  # Prune when at 80% capacity
     pruning_count: int = 0
+    budget: ContextBudget = Field(default_factory=ContextBudget)
     memory: MemoryIntegration | None = None
 
     class Config:
@@ -96,6 +207,10 @@ class ContextWindow(BaseModel):
             f"total: {self.token_count}/{self.token_limit})"
         )
 
+        # Enforce per-category budgets before global pruning so a flood of
+        # tool results can't crowd out conversation history (or vice versa)
+        self.enforce_budget()
+
         # Check if we need to prune
         if self.should_prune():
             logger.info(f"Context at {self._get_usage_percent():.1f}% - triggering pruning")
@@ -103,6 +218,57 @@ class ContextWindow(BaseModel):
 
         return item
 
+    def category_usage(self) -> dict[ContextCategory, int]:
+        """Current token usage per budget category."""
+        usage = {category: 0 for category in ContextCategory}
+        for item in self.items:
+            usage[CATEGORY_FOR_TYPE[item.item_type]] += item.token_cost
+        return usage
+
+    def enforce_budget(self) -> int:
+        """Trim categories that exceed their allocated window share.
+
+        Retrieval content (re-queryable) is trimmed before history;
+        instructions are never trimmed here. Within a category the oldest
+        non-sticky items go first.
+
+        Returns:
+            Number of tokens removed.
+        """
+        allocations = self.budget.allocate(self.token_limit)
+        usage = self.category_usage()
+        removed_tokens = 0
+
+        for category in (ContextCategory.RETRIEVAL, ContextCategory.HISTORY):
+            over = usage[category] - allocations[category]
+            if over <= 0:
+                continue
+
+            # Oldest first
+            candidates = [
+                item
+                for item in self.items
+                if CATEGORY_FOR_TYPE[item.item_type] == category and not item.sticky
+            ]
+            for item in candidates:
+                if over <= 0:
+                    break
+                self._summarize_to_episodic(item)
+                self.items.remove(item)
+                over -= item.token_cost
+                removed_tokens += item.token_cost
+                logger.debug(
+                    f"Budget trim: dropped {item.item_type} "
+                    f"({item.token_cost} tokens from {category})"
+                )
+
+        if removed_tokens:
+            self.token_count -= removed_tokens
+            logger.info(
+                f"Category budgets enforced: {removed_tokens} tokens trimmed"
+            )
+        return removed_tokens
+
     def should_prune(self) -> bool:
         """Check if context should be pruned."""
         return self.token_count >= (self.token_limit * self.pruning_threshold)
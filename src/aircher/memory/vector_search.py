@@ -1,13 +1,117 @@
 """ChromaDB-based vector search for semantic code retrieval."""
 
 import asyncio
+import re
 from pathlib import Path
-from typing import Any
+from typing import Any, Callable
 
 import chromadb
 from chromadb.config import Settings
 from sentence_transformers import SentenceTransformer
 
+# Called during model download with (description, bytes_done, bytes_total)
+ProgressCallback = Callable[[str, int, int], None]
+
+# Definition-line patterns per language for cheap symbol attribution:
+# good enough for result labels without running tree-sitter at index time
+_SYMBOL_PATTERNS = {
+    "python": re.compile(r"^\s*(?:async\s+)?(?:def|class)\s+([A-Za-z_]\w*)"),
+    "rust": re.compile(
+        r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?"
+        r"(?:fn|struct|enum|trait)\s+([A-Za-z_]\w*)"
+    ),
+    "javascript": re.compile(
+        r"^\s*(?:export\s+)?(?:async\s+)?(?:function|class)\s+([A-Za-z_$][\w$]*)"
+    ),
+    "typescript": re.compile(
+        r"^\s*(?:export\s+)?(?:async\s+)?(?:function|class)\s+([A-Za-z_$][\w$]*)"
+    ),
+    "go": re.compile(r"^\s*func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)"),
+}
+
+
+def nearest_symbol(
+    lines: list[str], start: int, end: int, language: str
+) -> str | None:
+    """Symbol a chunk of source lines belongs to.
+
+    The first definition inside the chunk wins; a chunk with none is a
+    body continuation, so the last definition above it is used instead.
+    start/end are 0-based slice indices into lines.
+    """
+    pattern = _SYMBOL_PATTERNS.get(language)
+    if pattern is None:
+        return None
+    for line in lines[start:end]:
+        match = pattern.match(line)
+        if match:
+            return match.group(1)
+    for line in reversed(lines[:start]):
+        match = pattern.match(line)
+        if match:
+            return match.group(1)
+    return None
+
+
+def ensure_model_available(
+    model_name: str,
+    progress_callback: ProgressCallback | None = None,
+) -> None:
+    """Download the embedding model if it isn't cached yet.
+
+    The first run pulls ~90MB from HuggingFace; without feedback that looks
+    like a hung process. With a callback, download progress is reported as
+    (description, bytes_done, bytes_total) so callers can render a spinner
+    or progress bar before their UI is up.
+    """
+    try:
+        from huggingface_hub import snapshot_download
+        from huggingface_hub.utils import tqdm as hf_tqdm
+    except ImportError:
+        # Let sentence-transformers handle acquisition itself
+        return
+
+    try:
+        snapshot_download(model_name, local_files_only=True)
+        return  # Already cached
+    except Exception:
+        pass
+
+    if progress_callback is None:
+        snapshot_download(model_name)
+        return
+
+    class _CallbackTqdm(hf_tqdm):
+        def update(self, n: float | None = 1) -> bool | None:
+            result = super().update(n)
+            progress_callback(
+                str(self.desc or model_name), int(self.n), int(self.total or 0)
+            )
+            return result
+
+    snapshot_download(model_name, tqdm_class=_CallbackTqdm)
+
+
+# Per-language markers that indicate documented code. Used by the optional
+# documentation boost in search ranking.
+DOC_COMMENT_MARKERS: dict[str, list[str]] = {
+    "python": ['"""', "'''", "# "],
+    "rust": ["///", "//!", "// "],
+    "javascript": ["/**", "// "],
+    "typescript": ["/**", "// "],
+    "go": ["// "],
+    "java": ["/**", "// "],
+    "cpp": ["/**", "// "],
+    "c": ["/*", "// "],
+}
+
+# Words that suggest a conceptual (natural-language) query rather than a
+# symbol/code lookup.
+_CONCEPTUAL_QUERY_WORDS = {
+    "how", "why", "what", "when", "where", "explain", "handle", "handles",
+    "works", "implement", "implements", "logic", "flow", "purpose",
+}
+
 
 class VectorSearch:
     """Semantic code search using ChromaDB and sentence-transformers."""
@@ -16,12 +120,22 @@ class VectorSearch:
         self,
         persist_directory: Path | None = None,
         model_name: str = "sentence-transformers/all-MiniLM-L6-v2",
+        progress_callback: ProgressCallback | None = None,
+        allow_mismatch: bool = False,
     ):
         """Initialize ChromaDB client and embedding model.
 
         Args:
             persist_directory: Directory to persist ChromaDB data. If None, uses in-memory.
             model_name: Sentence-transformers model name (default: all-MiniLM-L6-v2, 384 dims).
+            progress_callback: Optional (description, bytes_done, bytes_total)
+                callback for first-run model download progress.
+            allow_mismatch: Skip the embedding-model compatibility check
+                (used when the caller is about to rebuild the index).
+
+        Raises:
+            RuntimeError: If the existing index was built with a different
+                embedding model and allow_mismatch is False.
         """
         if persist_directory is None:
             self.client = chromadb.Client(Settings(is_persistent=False))
@@ -34,14 +148,50 @@ class VectorSearch:
                 )
             )
 
-        # Load sentence-transformers model
+        # Load sentence-transformers model (downloading with progress on
+        # first run)
+        ensure_model_available(model_name, progress_callback)
         self.model = SentenceTransformer(model_name)
+        self.model_name = model_name
 
-        # Create or get collection
+        # Create or get collection, stamped with the embedding model so a
+        # later model switch can't silently query a mismatched index
         self.collection = self.client.get_or_create_collection(
             name="code_snippets",
-            metadata={"hnsw:space": "cosine"},
+            metadata=self._collection_metadata(),
         )
+        if not allow_mismatch:
+            self._verify_embedding_model()
+
+    def _collection_metadata(self) -> dict[str, Any]:
+        """Collection metadata including the embedding model stamp."""
+        return {
+            "hnsw:space": "cosine",
+            "embedding_model": self.model_name,
+            "embedding_dimension": self.model.get_sentence_embedding_dimension(),
+        }
+
+    def _verify_embedding_model(self) -> None:
+        """Refuse a non-empty index built with a different embedding model.
+
+        ChromaDB doesn't error on a model/dimension mismatch - queries
+        just return garbage similarities - so fail loudly instead.
+        """
+        metadata = self.collection.metadata or {}
+        indexed_model = metadata.get("embedding_model")
+        if indexed_model is None or self.collection.count() == 0:
+            # Empty index, or one from before model stamping; nothing to
+            # protect (legacy indexes get stamped on the next rebuild)
+            return
+        dimension = self.model.get_sentence_embedding_dimension()
+        indexed_dimension = metadata.get("embedding_dimension")
+        if indexed_model != self.model_name or indexed_dimension != dimension:
+            raise RuntimeError(
+                f"Index was built with {indexed_model} "
+                f"({indexed_dimension} dims) but the current embedding "
+                f"model is {self.model_name} ({dimension} dims); "
+                "run `aircher search index --force` to rebuild"
+            )
 
     def index_code_snippet(
         self,
@@ -97,6 +247,7 @@ class VectorSearch:
         query: str,
         n_results: int = 10,
         filter_language: str | None = None,
+        prefer_documented: bool = False,
     ) -> list[dict]:
         """Search for semantically similar code snippets.
 
@@ -104,6 +255,9 @@ class VectorSearch:
             query: Natural language or code query.
             n_results: Number of results to return.
             filter_language: Optional language filter (python, rust, etc.).
+            prefer_documented: When True and the query looks conceptual,
+                re-rank results to slightly boost chunks containing doc
+                comments/docstrings.
 
         Returns:
             List of matching snippets with content, metadata, and similarity scores.
@@ -136,8 +290,125 @@ class VectorSearch:
                 }
             )
 
+        if prefer_documented and self.is_conceptual_query(query):
+            formatted_results = self._boost_documented(formatted_results)
+
         return formatted_results
 
+    @staticmethod
+    def is_conceptual_query(query: str) -> bool:
+        """Heuristic check whether a query is conceptual rather than a symbol lookup.
+
+        Symbol-ish queries (containing code punctuation or a single token)
+        should not get the documentation boost.
+        """
+        if any(char in query for char in "(){}[]:;=_"):
+            return False
+
+        words = query.lower().split()
+        if len(words) < 2:
+            return False
+
+        return len(words) >= 4 or any(word in _CONCEPTUAL_QUERY_WORDS for word in words)
+
+    @staticmethod
+    def doc_comment_density(content: str, language: str) -> float:
+        """Fraction of lines that carry doc comments/docstrings for a language."""
+        markers = DOC_COMMENT_MARKERS.get(language)
+        if not markers:
+            return 0.0
+
+        lines = [line.strip() for line in content.splitlines() if line.strip()]
+        if not lines:
+            return 0.0
+
+        documented = sum(
+            1 for line in lines if any(marker in line for marker in markers)
+        )
+        return documented / len(lines)
+
+    def _boost_documented(self, results: list[dict]) -> list[dict]:
+        """Re-rank results, boosting chunks with doc comments.
+
+        The boost is small (up to +0.1 similarity at full density) so it
+        reorders near-ties rather than overriding semantic relevance. The
+        adjustment is recorded in each result for display/debugging.
+        """
+        for result in results:
+            metadata = result.get("metadata", {})
+            density = self.doc_comment_density(
+                result.get("content", ""), metadata.get("language", "unknown")
+            )
+            boost = 0.1 * density
+            if boost > 0:
+                result["doc_boost"] = round(boost, 4)
+                result["similarity"] = result["similarity"] + boost
+
+        results.sort(key=lambda r: r["similarity"], reverse=True)
+        return results
+
+    async def rerank(self, query: str, results: list[dict], llm: Any) -> list[dict]:
+        """Reorder results by LLM-judged relevance to the query.
+
+        Sends truncated snippets to the model with a compact scoring prompt
+        and sorts by the returned 0-10 scores (recorded as rerank_score).
+        Noticeably slower and costlier than pure vector ranking; on any
+        parse/model failure the original ordering is returned unchanged.
+        """
+        if len(results) < 2:
+            return results
+
+        snippets = []
+        for i, result in enumerate(results, start=1):
+            metadata = result.get("metadata", {})
+            content = result.get("content", "")[:400]
+            snippets.append(
+                f"[{i}] {metadata.get('file_path', '?')}\n{content}"
+            )
+
+        prompt = (
+            f"Score each code snippet's relevance to the query on a 0-10 scale.\n"
+            f"Query: {query}\n\n"
+            + "\n\n".join(snippets)
+            + f"\n\nRespond with only a JSON array of {len(results)} numbers, "
+            "one score per snippet in order."
+        )
+
+        try:
+            response = await llm.ainvoke(prompt)
+            text = response.content if hasattr(response, "content") else str(response)
+            scores = self._parse_rerank_scores(str(text), len(results))
+        except Exception:
+            scores = None
+
+        if scores is None:
+            return results
+
+        for result, score in zip(results, scores):
+            result["rerank_score"] = score
+        return sorted(results, key=lambda r: r["rerank_score"], reverse=True)
+
+    @staticmethod
+    def _parse_rerank_scores(text: str, count: int) -> list[float] | None:
+        """Extract a JSON score array from a model response.
+
+        Tolerates surrounding prose/code fences; returns None when no array
+        of the expected length is found.
+        """
+        import json
+        import re
+
+        for match in re.finditer(r"\[[^\[\]]*\]", text):
+            try:
+                values = json.loads(match.group(0))
+            except ValueError:
+                continue
+            if len(values) == count and all(
+                isinstance(v, (int, float)) for v in values
+            ):
+                return [float(v) for v in values]
+        return None
+
     async def index_codebase(
         self,
         root_path: Path,
@@ -181,6 +452,98 @@ class VectorSearch:
 
         return stats
 
+    async def index_changed(
+        self,
+        root_path: Path,
+        ref: str = "HEAD",
+        languages: list[str] | None = None,
+        chunk_size: int = 50,
+        overlap: int = 5,
+    ) -> dict[str, int] | None:
+        """Reindex only files changed since a git ref.
+
+        Much faster than a full scan after a handful of edits: changed and
+        untracked files are reindexed, deleted ones purged. Returns None
+        outside a git repo - callers should fall back to index_codebase.
+
+        Returns:
+            Statistics: {files_indexed, files_purged, snippets_indexed, errors}.
+        """
+        from ..project import changed_files
+
+        names = changed_files(root_path, ref)
+        if names is None:
+            return None
+
+        if languages is None:
+            languages = [".py", ".rs", ".js", ".ts", ".go", ".java", ".cpp", ".c"]
+
+        stats = {
+            "files_indexed": 0,
+            "files_purged": 0,
+            "snippets_indexed": 0,
+            "errors": 0,
+        }
+        for name in names:
+            file_path = root_path / name
+            if file_path.suffix not in languages:
+                continue
+            # Drop stale chunks first so a reindex replaces, not appends
+            self.remove_file(str(file_path))
+            if not file_path.exists():
+                stats["files_purged"] += 1
+                continue
+            try:
+                await self._index_file(file_path, chunk_size, overlap)
+                stats["files_indexed"] += 1
+            except Exception as e:
+                stats["errors"] += 1
+                print(f"Error indexing {file_path}: {e}")
+
+        stats["snippets_indexed"] = self.collection.count()
+        return stats
+
+    async def reindex_path(
+        self,
+        path: Path,
+        languages: list[str] | None = None,
+        chunk_size: int = 50,
+        overlap: int = 5,
+    ) -> dict[str, int]:
+        """Re-chunk and re-embed one file or directory right now.
+
+        Manual freshness control for targeted paths between background
+        refreshes: stale chunks are dropped first so the reindex
+        replaces rather than appends.
+
+        Returns:
+            Statistics: {files_indexed, errors}.
+        """
+        if languages is None:
+            languages = [".py", ".rs", ".js", ".ts", ".go", ".java", ".cpp", ".c"]
+
+        if path.is_file():
+            files = [path]
+        else:
+            files = []
+            for ext in languages:
+                files.extend(path.rglob(f"*{ext}"))
+
+        stats = {"files_indexed": 0, "errors": 0}
+        for file_path in files:
+            self.remove_file(str(file_path))
+            try:
+                await self._index_file(file_path, chunk_size, overlap)
+                stats["files_indexed"] += 1
+            except Exception as e:
+                stats["errors"] += 1
+                print(f"Error indexing {file_path}: {e}")
+        return stats
+
+    def remove_file(self, file_path: str) -> None:
+        """Remove all indexed chunks for a file."""
+        self.collection.delete(where={"file_path": file_path})
+
     async def _index_file(self, file_path: Path, chunk_size: int, overlap: int) -> None:
         """Index a single file by splitting into chunks."""
         # Read file
@@ -192,6 +555,8 @@ class VectorSearch:
 
         lines = content.splitlines()
         language = self._detect_language(file_path)
+        # Recorded so index_status can flag files edited since indexing
+        file_mtime = file_path.stat().st_mtime
 
         # Split into chunks with overlap
         i = 0
@@ -200,12 +565,19 @@ class VectorSearch:
             chunk = "\n".join(lines[i:end])
 
             if chunk.strip():  # Skip empty chunks
+                chunk_metadata: dict[str, Any] = {"file_mtime": file_mtime}
+                # Symbol attribution makes result lines scannable
+                # ("in parse_config" instead of bare line ranges)
+                symbol = nearest_symbol(lines, i, end, language)
+                if symbol:
+                    chunk_metadata["symbol"] = symbol
                 self.index_code_snippet(
                     file_path=str(file_path),
                     content=chunk,
                     start_line=i + 1,
                     end_line=end,
                     language=language,
+                    metadata=chunk_metadata,
                 )
 
             i += chunk_size - overlap
@@ -231,12 +603,54 @@ class VectorSearch:
         }
         return ext_to_lang.get(file_path.suffix, "unknown")
 
+    def index_status(self) -> list[dict[str, Any]]:
+        """Per-file index freshness report.
+
+        Compares the file mtime recorded at indexing time with the current
+        on-disk mtime. Files indexed before mtimes were recorded report
+        stale (a re-index fixes that).
+
+        Returns:
+            One entry per indexed file: {file_path, chunks, stale, missing}.
+        """
+        records = self.collection.get(include=["metadatas"])
+        by_file: dict[str, dict[str, Any]] = {}
+        for metadata in records["metadatas"]:
+            file_path = metadata.get("file_path", "?")
+            entry = by_file.setdefault(
+                file_path, {"file_path": file_path, "chunks": 0, "indexed_mtime": 0.0}
+            )
+            entry["chunks"] += 1
+            entry["indexed_mtime"] = max(
+                entry["indexed_mtime"], float(metadata.get("file_mtime", 0.0))
+            )
+
+        report = []
+        for entry in sorted(by_file.values(), key=lambda e: e["file_path"]):
+            path = Path(entry["file_path"])
+            missing = not path.exists()
+            if missing:
+                stale = True
+            else:
+                # mtime comparison needs a small tolerance for filesystems
+                # with coarse timestamps
+                stale = path.stat().st_mtime > entry["indexed_mtime"] + 1e-3
+            report.append(
+                {
+                    "file_path": entry["file_path"],
+                    "chunks": entry["chunks"],
+                    "stale": stale,
+                    "missing": missing,
+                }
+            )
+        return report
+
     def clear(self) -> None:
         """Clear all indexed snippets."""
         self.client.delete_collection("code_snippets")
         self.collection = self.client.get_or_create_collection(
             name="code_snippets",
-            metadata={"hnsw:space": "cosine"},
+            metadata=self._collection_metadata(),
         )
 
     def count(self) -> int:
@@ -1,6 +1,12 @@
 """Model routing and cost tracking."""
 
+import hashlib
+import os
+import time
+from collections import deque
+from collections.abc import AsyncIterator
 from datetime import datetime
+from email.utils import parsedate_to_datetime
 from enum import Enum
 from typing import Any
 
@@ -10,6 +16,8 @@ from langchain_openai import ChatOpenAI
 from loguru import logger
 from pydantic import BaseModel, Field
 
+from .streaming import StreamChunk, stream_openai_compatible
+
 
 class ModelProvider(str, Enum):
     """Supported LLM providers."""
@@ -40,8 +48,25 @@ class ModelConfig(BaseModel):
     cost_per_1k_output: float  # USD per 1k output tokens
     context_window: int
     supports_streaming: bool = True
+    supports_vision: bool = False
+    supports_tools: bool = True
+    supports_reasoning: bool = False
+    max_output_tokens: int = 4096
     max_retries: int = 3
 
+    def capability_badges(self) -> str:
+        """Short badge string for model listings (e.g. "vision tools")."""
+        badges = []
+        if self.supports_vision:
+            badges.append("vision")
+        if self.supports_tools:
+            badges.append("tools")
+        if self.supports_reasoning:
+            badges.append("reasoning")
+        if self.supports_streaming:
+            badges.append("stream")
+        return " ".join(badges)
+
 
 # Pricing as of 2025-01 (verify before production use)
 SUPPORTED_MODELS = {
@@ -53,6 +78,8 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.0025,
         cost_per_1k_output=0.010,
         context_window=128000,
+        supports_vision=True,
+        max_output_tokens=16384,
     ),
     "gpt-4o-mini": ModelConfig(
         name="gpt-4o-mini",
@@ -61,6 +88,8 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.00015,
         cost_per_1k_output=0.0006,
         context_window=128000,
+        supports_vision=True,
+        max_output_tokens=16384,
     ),
     "gpt-4": ModelConfig(
         name="gpt-4",
@@ -78,6 +107,9 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.015,
         cost_per_1k_output=0.075,
         context_window=200000,
+        supports_vision=True,
+        supports_reasoning=True,
+        max_output_tokens=32000,
     ),
     "claude-sonnet-4-20250514": ModelConfig(
         name="claude-sonnet-4-20250514",
@@ -86,6 +118,9 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.003,
         cost_per_1k_output=0.015,
         context_window=200000,
+        supports_vision=True,
+        supports_reasoning=True,
+        max_output_tokens=64000,
     ),
     "claude-haiku-4-20250514": ModelConfig(
         name="claude-haiku-4-20250514",
@@ -94,6 +129,8 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.0008,
         cost_per_1k_output=0.004,
         context_window=200000,
+        supports_vision=True,
+        max_output_tokens=8192,
     ),
     # Ollama (local models - zero cost)
     "ollama/llama3.2": ModelConfig(
@@ -132,6 +169,7 @@ SUPPORTED_MODELS = {
         cost_per_1k_output=0.0,
         context_window=16384,
         supports_streaming=True,
+        supports_tools=False,
     ),
     # OpenRouter - access to many models via single API
     "anthropic/claude-sonnet-4.5": ModelConfig(
@@ -141,6 +179,9 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.003,
         cost_per_1k_output=0.015,
         context_window=200000,
+        supports_vision=True,
+        supports_reasoning=True,
+        max_output_tokens=64000,
     ),
     "anthropic/claude-opus-4": ModelConfig(
         name="anthropic/claude-opus-4",
@@ -149,6 +190,9 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.015,
         cost_per_1k_output=0.075,
         context_window=200000,
+        supports_vision=True,
+        supports_reasoning=True,
+        max_output_tokens=32000,
     ),
     "openai/gpt-4o": ModelConfig(
         name="openai/gpt-4o",
@@ -157,15 +201,44 @@ SUPPORTED_MODELS = {
         cost_per_1k_input=0.0025,
         cost_per_1k_output=0.010,
         context_window=128000,
+        supports_vision=True,
+        max_output_tokens=16384,
     ),
 }
 
+# Auxiliary/housekeeping task types that route to the configured
+# utility_model instead of the main chat model
+UTILITY_TASKS = {"summarization", "context_analysis", "simple_query"}
+
+
+# Share of the normal input price saved on cache reads. Billing differs
+# per provider: Anthropic charges ~10% of the fresh-token rate, OpenAI
+# 50%. Providers without confirmed cache pricing get no discount rather
+# than an overstated savings figure.
+CACHE_READ_DISCOUNTS = {
+    ModelProvider.ANTHROPIC: 0.9,
+    ModelProvider.OPENAI: 0.5,
+}
+
+# Valid /effort levels, lowest latency first
+REASONING_EFFORTS = ("low", "medium", "high")
+
+# Anthropic takes a thinking-token budget rather than a named effort
+_ANTHROPIC_THINKING_BUDGETS = {"low": 2048, "medium": 8192, "high": 16384}
+
 
 class UsageStats(BaseModel):
     """Token usage statistics."""
 
     input_tokens: int = 0
     output_tokens: int = 0
+    # Reasoning/thinking tokens (subset of output, priced separately by
+    # some providers); tracked for reporting
+    reasoning_tokens: int = 0
+    # Input tokens served from the provider's prompt cache, and what the
+    # discount saved versus fresh tokens
+    cached_input_tokens: int = 0
+    cache_savings: float = 0.0
     total_tokens: int = 0
     estimated_cost: float = 0.0
     model_name: str = ""
@@ -185,6 +258,8 @@ class SessionCostTracker(BaseModel):
         model_name: str,
         input_tokens: int,
         output_tokens: int,
+        reasoning_tokens: int = 0,
+        cached_input_tokens: int = 0,
     ) -> UsageStats:
         """Add usage statistics for a model call."""
         if model_name not in SUPPORTED_MODELS:
@@ -192,20 +267,32 @@ class SessionCostTracker(BaseModel):
             return UsageStats(
                 input_tokens=input_tokens,
                 output_tokens=output_tokens,
+                reasoning_tokens=reasoning_tokens,
+                cached_input_tokens=cached_input_tokens,
                 total_tokens=input_tokens + output_tokens,
                 model_name=model_name,
             )
 
         model_config = SUPPORTED_MODELS[model_name]
 
-        # Calculate cost
-        input_cost = (input_tokens / 1000.0) * model_config.cost_per_1k_input
+        # Calculate cost; cache reads are billed at the provider's discount
+        discount = CACHE_READ_DISCOUNTS.get(model_config.provider, 0.0)
+        cached = min(cached_input_tokens, input_tokens)
+        fresh = input_tokens - cached
+        input_price = model_config.cost_per_1k_input
+        input_cost = (fresh / 1000.0) * input_price + (cached / 1000.0) * (
+            input_price * (1 - discount)
+        )
+        cache_savings = (cached / 1000.0) * input_price * discount
         output_cost = (output_tokens / 1000.0) * model_config.cost_per_1k_output
         total_cost = input_cost + output_cost
 
         usage = UsageStats(
             input_tokens=input_tokens,
             output_tokens=output_tokens,
+            reasoning_tokens=reasoning_tokens,
+            cached_input_tokens=cached,
+            cache_savings=cache_savings,
             total_tokens=input_tokens + output_tokens,
             estimated_cost=total_cost,
             model_name=model_name,
@@ -236,18 +323,342 @@ class SessionCostTracker(BaseModel):
             sum(u.output_tokens for u in usages)
             for usages in self.usage_by_model.values()
         )
+        total_reasoning = sum(
+            sum(u.reasoning_tokens for u in usages)
+            for usages in self.usage_by_model.values()
+        )
+        total_cached = sum(
+            sum(u.cached_input_tokens for u in usages)
+            for usages in self.usage_by_model.values()
+        )
+        cache_savings = sum(
+            sum(u.cache_savings for u in usages)
+            for usages in self.usage_by_model.values()
+        )
+
+        by_model = {
+            model_name: {
+                "input_tokens": sum(u.input_tokens for u in usages),
+                "output_tokens": sum(u.output_tokens for u in usages),
+                "cost": round(sum(u.estimated_cost for u in usages), 4),
+                "calls": len(usages),
+            }
+            for model_name, usages in self.usage_by_model.items()
+        }
 
         return {
             "session_id": self.session_id,
             "total_cost": round(self.total_cost, 4),
             "total_input_tokens": total_input,
             "total_output_tokens": total_output,
+            "total_reasoning_tokens": total_reasoning,
+            "total_cached_input_tokens": total_cached,
+            "cache_savings": round(cache_savings, 4),
             "total_tokens": total_input + total_output,
             "models_used": list(self.usage_by_model.keys()),
+            "by_model": by_model,
             "call_count": sum(len(usages) for usages in self.usage_by_model.values()),
         }
 
 
+# API key env vars by provider; local providers need no credentials
+_PROVIDER_API_KEYS = {
+    ModelProvider.OPENAI: "OPENAI_API_KEY",
+    ModelProvider.ANTHROPIC: "ANTHROPIC_API_KEY",
+    ModelProvider.OPENROUTER: "OPENROUTER_API_KEY",
+}
+
+
+def provider_is_authenticated(provider: ModelProvider) -> bool:
+    """Check whether a provider has credentials available."""
+    env_key = _PROVIDER_API_KEYS.get(provider)
+    if env_key is None:
+        return True  # ollama/vllm run without API keys
+    return bool(os.getenv(env_key))
+
+
+def reasoning_kwargs(
+    config: ModelConfig, reasoning_effort: str | None
+) -> dict[str, Any]:
+    """Translate an effort level into provider-specific model kwargs.
+
+    OpenAI takes a named reasoning_effort; Anthropic takes an explicit
+    thinking-token budget. Models without reasoning support (and
+    providers with no such parameter) get nothing - the effort is
+    silently inapplicable rather than an error.
+    """
+    if reasoning_effort is None or not config.supports_reasoning:
+        return {}
+    if reasoning_effort not in REASONING_EFFORTS:
+        logger.warning(f"Unknown reasoning effort {reasoning_effort!r}; ignoring")
+        return {}
+
+    if config.provider == ModelProvider.OPENAI:
+        return {"model_kwargs": {"reasoning_effort": reasoning_effort}}
+    if config.provider == ModelProvider.ANTHROPIC:
+        return {
+            "thinking": {
+                "type": "enabled",
+                "budget_tokens": _ANTHROPIC_THINKING_BUDGETS[reasoning_effort],
+            }
+        }
+    return {}
+
+
+def model_for_task(
+    task_type: str,
+    default_model: str | None = None,
+    use_tier_heuristic: bool = True,
+) -> str:
+    """Resolve which model a task category should run on.
+
+    Resolution order: the explicit settings.task_models mapping, the
+    configured utility_model for housekeeping tasks, tier heuristics
+    (unless disabled), then the caller's default. Central so every
+    LLM-calling subsystem routes consistently.
+    """
+    from ..config import get_settings
+
+    settings = get_settings()
+
+    # Explicit per-task mapping wins over everything
+    override = settings.task_models.get(task_type)
+    if override:
+        if override in SUPPORTED_MODELS:
+            return override
+        logger.warning(
+            f"task_models maps {task_type} to unknown model {override}; ignoring"
+        )
+
+    # Housekeeping tasks honor the configured utility model so chat can
+    # run on an expensive model while summarization stays cheap
+    if task_type in UTILITY_TASKS:
+        utility_model = settings.utility_model
+        if utility_model and utility_model in SUPPORTED_MODELS:
+            return utility_model
+
+    if use_tier_heuristic:
+        routing = {
+            "main_agent": ModelTier.MEDIUM,  # gpt-4o, sonnet
+            "sub_agent": ModelTier.SMALL,  # gpt-4o-mini, haiku
+            "simple_query": ModelTier.SMALL,
+            "complex_reasoning": ModelTier.LARGE,  # opus, gpt-4
+            "code_generation": ModelTier.MEDIUM,
+            "code_review": ModelTier.SMALL,
+            "commit_message": ModelTier.SMALL,
+            "search_rerank": ModelTier.SMALL,
+            "summarization": ModelTier.SMALL,
+            "context_analysis": ModelTier.SMALL,
+        }
+        desired_tier = routing.get(task_type, ModelTier.MEDIUM)
+        for name, config in SUPPORTED_MODELS.items():
+            if config.tier == desired_tier:
+                return name
+
+    return default_model or settings.default_model
+
+
+# Provider phrasings for "request exceeded the context window"; there is
+# no structured error code shared across SDKs, so match message text
+_CONTEXT_LENGTH_MARKERS = (
+    "context_length_exceeded",
+    "context length",
+    "maximum context",
+    "prompt is too long",
+    "too many tokens",
+    "input is too long",
+)
+
+
+def is_context_length_error(error: Exception) -> bool:
+    """Check whether a provider error means the request was too large.
+
+    Context overflow is distinct from other bad requests because it's
+    recoverable: trim history (or compact) and retry.
+    """
+    text = str(error).lower()
+    return any(marker in text for marker in _CONTEXT_LENGTH_MARKERS)
+
+
+# Headers providers use to say when a rate-limit window resets
+_RETRY_AFTER_HEADERS = ("retry-after-ms", "retry-after")
+
+
+def rate_limit_delay(error: Exception, max_seconds: float = 120.0) -> float | None:
+    """Seconds until a rate-limited request may be retried, when known.
+
+    Provider SDK exceptions carry the HTTP response, so a 429's
+    Retry-After (or retry-after-ms) header can be read off the error and
+    turned into a countdown instead of a generic "wait a moment".
+    Returns None for errors that aren't rate limits or don't say when
+    the window resets.
+    """
+    text = str(error).lower()
+    if "rate limit" not in text and "rate_limit" not in text and "429" not in text:
+        return None
+
+    headers = getattr(getattr(error, "response", None), "headers", None)
+    if not headers:
+        return None
+    for name in _RETRY_AFTER_HEADERS:
+        value = headers.get(name)
+        if value is None:
+            continue
+        try:
+            seconds = float(value)
+        except (TypeError, ValueError):
+            # Retry-After may also be an HTTP-date
+            try:
+                when = parsedate_to_datetime(str(value))
+            except (TypeError, ValueError):
+                continue
+            seconds = (when - datetime.now(when.tzinfo)).total_seconds()
+        if name.endswith("-ms"):
+            seconds /= 1000.0
+        if seconds > 0:
+            return min(seconds, max_seconds)
+    return None
+
+
+class ProviderHealth:
+    """Track recent provider failures so fallback skips known-bad providers.
+
+    A failure puts the provider in a cooldown window; once it elapses the
+    provider becomes eligible again (outages are usually transient).
+    """
+
+    def __init__(self, cooldown_seconds: float = 60.0):
+        self.cooldown_seconds = cooldown_seconds
+        self._failed_at: dict[ModelProvider, float] = {}
+
+    def record_failure(self, provider: ModelProvider) -> None:
+        """Mark a provider as unhealthy for the cooldown window."""
+        self._failed_at[provider] = time.monotonic()
+
+    def record_success(self, provider: ModelProvider) -> None:
+        """Clear any failure state for a provider."""
+        self._failed_at.pop(provider, None)
+
+    def is_healthy(self, provider: ModelProvider) -> bool:
+        """Check whether a provider is outside its failure cooldown."""
+        failed_at = self._failed_at.get(provider)
+        if failed_at is None:
+            return True
+        return time.monotonic() - failed_at >= self.cooldown_seconds
+
+
+class LatencyMonitor:
+    """Track per-model request latencies to spot consistently slow models.
+
+    After `threshold` consecutive requests that were slower than
+    `slow_seconds` (or failed outright), callers should suggest a faster
+    model in the same family; see suggest_downgrade().
+    """
+
+    def __init__(self, slow_seconds: float = 30.0, threshold: int = 3):
+        self.slow_seconds = slow_seconds
+        self.threshold = threshold
+        self._recent: dict[str, deque[float]] = {}
+        self._consecutive: dict[str, int] = {}
+
+    def record(self, model: str, seconds: float, failed: bool = False) -> None:
+        """Record one request's latency (failures count as slow)."""
+        self._recent.setdefault(model, deque(maxlen=20)).append(seconds)
+        if failed or (self.slow_seconds > 0 and seconds >= self.slow_seconds):
+            self._consecutive[model] = self._consecutive.get(model, 0) + 1
+        else:
+            self._consecutive[model] = 0
+
+    def consecutive_slow(self, model: str) -> int:
+        """Current run of consecutive slow/failed requests for a model."""
+        return self._consecutive.get(model, 0)
+
+    def downgrade_due(self, model: str) -> bool:
+        """Whether the model has been slow often enough to suggest a switch."""
+        if self.slow_seconds <= 0 or self.threshold <= 0:
+            return False
+        return self._consecutive.get(model, 0) >= self.threshold
+
+    def reset(self, model: str) -> None:
+        """Clear the slow streak (after a suggestion has been surfaced)."""
+        self._consecutive[model] = 0
+
+
+class StreamCostWatcher:
+    """Track a streaming response's estimated cost against a session budget.
+
+    Pre-send budget checks can't catch a long response crossing the limit
+    mid-stream; this accumulates estimated output cost chunk by chunk and
+    reports each threshold (80%, 100%) the first time it's crossed. With
+    hard_stop, crossing 100% also asks the caller to stop the stream.
+    """
+
+    WARN_FRACTION = 0.8
+
+    def __init__(
+        self,
+        model_name: str,
+        budget: float,
+        spent: float = 0.0,
+        hard_stop: bool = False,
+    ):
+        config = SUPPORTED_MODELS.get(model_name)
+        self._output_price = config.cost_per_1k_output if config else 0.0
+        self.budget = budget
+        self.spent = spent
+        self.hard_stop = hard_stop
+        self.stop = False
+        self._output_chars = 0
+        self._warned: set[float] = set()
+
+    def note_output(self, chars: int) -> str | None:
+        """Record streamed output; return a warning on a threshold crossing."""
+        if self.budget <= 0:
+            return None
+        self._output_chars += chars
+        cost = self.spent + (self._output_chars // 4) / 1000 * self._output_price
+
+        if cost >= self.budget and 1.0 not in self._warned:
+            self._warned.update((1.0, self.WARN_FRACTION))
+            if self.hard_stop:
+                self.stop = True
+                return f"session budget ${self.budget:.2f} reached - stopping stream"
+            return f"session cost passed its ${self.budget:.2f} budget"
+        if (
+            cost >= self.budget * self.WARN_FRACTION
+            and self.WARN_FRACTION not in self._warned
+        ):
+            self._warned.add(self.WARN_FRACTION)
+            return (
+                f"session cost passed {int(self.WARN_FRACTION * 100)}% "
+                f"of its ${self.budget:.2f} budget"
+            )
+        return None
+
+
+def suggest_downgrade(
+    model_name: str, overrides: dict[str, str] | None = None
+) -> str | None:
+    """Pick a faster model in the same family for a slow model.
+
+    An explicit override mapping wins; otherwise the first supported model
+    from the same provider in a smaller tier is chosen.
+    """
+    if overrides and model_name in overrides:
+        target = overrides[model_name]
+        return target if target in SUPPORTED_MODELS else None
+
+    config = SUPPORTED_MODELS.get(model_name)
+    if config is None:
+        return None
+    tiers = [ModelTier.LARGE, ModelTier.MEDIUM, ModelTier.SMALL, ModelTier.LOCAL]
+    for tier in tiers[tiers.index(config.tier) + 1 :]:
+        for name, candidate in SUPPORTED_MODELS.items():
+            if candidate.provider == config.provider and candidate.tier == tier:
+                return name
+    return None
+
+
 class ModelRouter:
     """Smart model router with cost tracking and fallback."""
 
@@ -264,9 +675,22 @@ class ModelRouter:
         # Cost tracking
         self.cost_tracker = SessionCostTracker(session_id=self.session_id)
 
+        # Recent-failure tracking so fallback skips known-bad providers
+        self.provider_health = ProviderHealth()
+
         # Fallback chain: large -> medium -> small -> local
         self.fallback_chain = self._build_fallback_chain()
 
+        # Models already warned about output-token clamping (warn once each)
+        self._clamp_warned: set[str] = set()
+
+        # Constructed chat clients, reused across requests so pooled
+        # HTTP connections (keep-alive) survive between messages instead
+        # of re-handshaking TLS every send. Keyed by call parameters plus
+        # an auth/header fingerprint, so a client is rebuilt only when
+        # credentials or header config actually change.
+        self._client_cache: dict[tuple[Any, ...], BaseChatModel] = {}
+
         logger.info(
             f"ModelRouter initialized: default={default_model}, fallback={enable_fallback}"
         )
@@ -294,6 +718,7 @@ class ModelRouter:
         model_name: str | None = None,
         temperature: float = 0.7,
         max_tokens: int | None = None,
+        reasoning_effort: str | None = None,
     ) -> BaseChatModel:
         """Get a configured LLM model.
 
@@ -301,6 +726,8 @@ class ModelRouter:
             model_name: Specific model to use (or default)
             temperature: Model temperature
             max_tokens: Maximum output tokens
+            reasoning_effort: low/medium/high thinking budget, translated
+                per provider; ignored for models without reasoning support
 
         Returns:
             Configured LangChain chat model
@@ -314,23 +741,41 @@ class ModelRouter:
             model_name = self.default_model
 
         config = SUPPORTED_MODELS[model_name]
+        max_tokens = self._clamp_output_tokens(model_name, config, max_tokens)
+        extra_headers = self._extra_headers(config.provider)
+        reasoning = reasoning_kwargs(config, reasoning_effort)
+
+        cache_key = (
+            model_name,
+            temperature,
+            max_tokens,
+            reasoning_effort,
+            self._auth_fingerprint(config.provider, extra_headers),
+        )
+        cached = self._client_cache.get(cache_key)
+        if cached is not None:
+            return cached
 
         try:
             if config.provider == ModelProvider.OPENAI:
-                return ChatOpenAI(
+                client: BaseChatModel = ChatOpenAI(
                     model=config.name,
                     temperature=temperature,
                     max_tokens=max_tokens,
+                    default_headers=extra_headers or None,
+                    **reasoning,
                 )
             elif config.provider == ModelProvider.ANTHROPIC:
-                return ChatAnthropic(
+                client = ChatAnthropic(
                     model=config.name,
                     temperature=temperature,
                     max_tokens=max_tokens or 4096,
+                    default_headers=extra_headers or None,
+                    **reasoning,
                 )
             elif config.provider == ModelProvider.OLLAMA:
                 # Ollama uses ChatOpenAI with custom base_url
-                return ChatOpenAI(
+                client = ChatOpenAI(
                     model=config.name.replace("ollama/", ""),
                     base_url="http://localhost:11434/v1",
                     api_key="ollama",  # Ollama doesn't need real API key
@@ -339,7 +784,7 @@ class ModelRouter:
                 )
             elif config.provider == ModelProvider.VLLM:
                 # vLLM on fedora via Tailscale (OpenAI-compatible API)
-                return ChatOpenAI(
+                client = ChatOpenAI(
                     model=config.name.replace("vllm/", ""),
                     base_url="http://100.93.39.25:8000/v1",
                     api_key="vllm",  # vLLM doesn't need real API key
@@ -348,9 +793,7 @@ class ModelRouter:
                 )
             elif config.provider == ModelProvider.OPENROUTER:
                 # OpenRouter - access to many models via single API
-                import os
-
-                return ChatOpenAI(
+                client = ChatOpenAI(
                     model=config.name,  # Keep full name (e.g., "anthropic/claude-sonnet-4.5")
                     base_url="https://openrouter.ai/api/v1",
                     api_key=os.getenv("OPENROUTER_API_KEY"),
@@ -359,26 +802,86 @@ class ModelRouter:
                     default_headers={
                         "HTTP-Referer": "https://github.com/nijaru/aircher",
                         "X-Title": "Aircher",
+                        **extra_headers,
                     },
                 )
             else:
                 raise ValueError(f"Unsupported provider: {config.provider}")
 
+            self._client_cache[cache_key] = client
+            return client
+
         except Exception as e:
             logger.error(f"Failed to initialize {model_name}: {e}")
+            self.provider_health.record_failure(config.provider)
 
             if self.enable_fallback:
                 return self._try_fallback(model_name, temperature, max_tokens)
             else:
                 raise
 
+    def _auth_fingerprint(
+        self, provider: ModelProvider, extra_headers: dict[str, str]
+    ) -> str:
+        """Fingerprint of the auth and header config a client is built with.
+
+        A changed API key or header set must rebuild the cached client
+        (clients capture credentials at construction); anything else
+        reuses it. Hashed so the cache never holds raw secret values.
+        """
+        from ..auth import PROVIDER_ENV_VARS
+
+        env_var = PROVIDER_ENV_VARS.get(provider.value)
+        key = os.getenv(env_var, "") if env_var else ""
+        material = repr((key, sorted(extra_headers.items())))
+        return hashlib.sha256(material.encode()).hexdigest()
+
+    def _extra_headers(self, provider: ModelProvider) -> dict[str, str]:
+        """Configured extra HTTP headers for a provider.
+
+        Needed behind proxies/gateways that route on org IDs or tags.
+        Only header names are ever logged; values may be sensitive.
+        """
+        from ..config import get_settings
+
+        headers = get_settings().provider_headers.get(provider.value, {})
+        if headers:
+            logger.debug(
+                f"Attaching extra headers for {provider.value}: {sorted(headers)}"
+            )
+        return dict(headers)
+
+    def _clamp_output_tokens(
+        self, model_name: str, config: ModelConfig, max_tokens: int | None
+    ) -> int | None:
+        """Clamp a requested output cap to what the model supports.
+
+        Sending max_tokens above the model's maximum output fails the
+        whole request; clamping with a one-time warning is friendlier.
+        """
+        if max_tokens is None or max_tokens <= config.max_output_tokens:
+            return max_tokens
+
+        if model_name not in self._clamp_warned:
+            self._clamp_warned.add(model_name)
+            logger.warning(
+                f"max_tokens={max_tokens} exceeds {model_name}'s output cap; "
+                f"clamping to {config.max_output_tokens}"
+            )
+        return config.max_output_tokens
+
     def _try_fallback(
         self,
         failed_model: str,
         temperature: float,
         max_tokens: int | None,
     ) -> BaseChatModel:
-        """Try fallback models when primary fails."""
+        """Try fallback models when the primary fails.
+
+        Walks the chain in order, skipping models whose provider has no
+        credentials or recently failed (health cooldown), and returns the
+        first one that initializes.
+        """
         # Find next model in fallback chain
         try:
             failed_idx = self.fallback_chain.index(failed_model)
@@ -386,67 +889,146 @@ class ModelRouter:
         except ValueError:
             remaining_models = self.fallback_chain
 
+        skipped: list[str] = []
         for fallback_model in remaining_models:
+            config = SUPPORTED_MODELS[fallback_model]
+
+            if not provider_is_authenticated(config.provider):
+                skipped.append(f"{fallback_model} (no credentials)")
+                continue
+            if not self.provider_health.is_healthy(config.provider):
+                skipped.append(f"{fallback_model} (provider cooling down)")
+                continue
+
+            capped = self._clamp_output_tokens(fallback_model, config, max_tokens)
+            extra_headers = self._extra_headers(config.provider)
+
             try:
                 logger.info(f"Trying fallback model: {fallback_model}")
-                config = SUPPORTED_MODELS[fallback_model]
 
                 if config.provider == ModelProvider.OPENAI:
-                    return ChatOpenAI(
+                    model = ChatOpenAI(
                         model=config.name,
                         temperature=temperature,
-                        max_tokens=max_tokens,
+                        max_tokens=capped,
+                        default_headers=extra_headers or None,
                     )
                 elif config.provider == ModelProvider.ANTHROPIC:
-                    return ChatAnthropic(
+                    model = ChatAnthropic(
                         model=config.name,
                         temperature=temperature,
-                        max_tokens=max_tokens or 4096,
+                        max_tokens=capped or 4096,
+                        default_headers=extra_headers or None,
                     )
                 elif config.provider == ModelProvider.OLLAMA:
-                    return ChatOpenAI(
+                    model = ChatOpenAI(
                         model=config.name.replace("ollama/", ""),
                         base_url="http://localhost:11434/v1",
                         api_key="ollama",
                         temperature=temperature,
-                        max_tokens=max_tokens,
+                        max_tokens=capped,
                     )
                 elif config.provider == ModelProvider.VLLM:
-                    return ChatOpenAI(
+                    model = ChatOpenAI(
                         model=config.name.replace("vllm/", ""),
                         base_url="http://100.93.39.25:8000/v1",
                         api_key="vllm",
                         temperature=temperature,
-                        max_tokens=max_tokens,
+                        max_tokens=capped,
                     )
                 elif config.provider == ModelProvider.OPENROUTER:
-                    import os
-
-                    return ChatOpenAI(
+                    model = ChatOpenAI(
                         model=config.name,
                         base_url="https://openrouter.ai/api/v1",
                         api_key=os.getenv("OPENROUTER_API_KEY"),
                         temperature=temperature,
-                        max_tokens=max_tokens,
+                        max_tokens=capped,
                         default_headers={
                             "HTTP-Referer": "https://github.com/nijaru/aircher",
                             "X-Title": "Aircher",
+                            **extra_headers,
                         },
                     )
+                else:
+                    skipped.append(f"{fallback_model} (unsupported provider)")
+                    continue
+
+                self.provider_health.record_success(config.provider)
+                return model
             except Exception as e:
                 logger.warning(f"Fallback {fallback_model} also failed: {e}")
+                self.provider_health.record_failure(config.provider)
                 continue
 
-        raise RuntimeError("All fallback models failed")
+        detail = f" (skipped: {', '.join(skipped)})" if skipped else ""
+        raise RuntimeError(f"All fallback providers exhausted{detail}")
 
     def track_usage(
         self,
         model_name: str,
         input_tokens: int,
         output_tokens: int,
+        reasoning_tokens: int = 0,
+        cached_input_tokens: int = 0,
     ) -> UsageStats:
         """Track token usage and cost for a model call."""
-        return self.cost_tracker.add_usage(model_name, input_tokens, output_tokens)
+        return self.cost_tracker.add_usage(
+            model_name,
+            input_tokens,
+            output_tokens,
+            reasoning_tokens=reasoning_tokens,
+            cached_input_tokens=cached_input_tokens,
+        )
+
+    # Self-hosted OpenAI-compatible endpoints where LangChain's wrapper adds
+    # nothing; streaming talks to them directly (see models.streaming)
+    _RAW_STREAM_ENDPOINTS = {
+        ModelProvider.OLLAMA: ("http://localhost:11434/v1", "ollama"),
+        ModelProvider.VLLM: ("http://100.93.39.25:8000/v1", "vllm"),
+    }
+
+    async def astream_chunks(
+        self,
+        model_name: str | None = None,
+        prompt: str = "",
+        temperature: float = 0.7,
+        max_tokens: int | None = None,
+    ) -> AsyncIterator[StreamChunk]:
+        """Stream a completion as normalized StreamChunks.
+
+        Raw OpenAI-compatible endpoints (vLLM, Ollama) are streamed over
+        SSE directly; managed providers go through their LangChain clients,
+        with each piece normalized into the same StreamChunk shape so
+        consumers never branch on provider.
+        """
+        model_name = model_name or self.default_model
+        config = SUPPORTED_MODELS.get(model_name)
+        endpoint = (
+            self._RAW_STREAM_ENDPOINTS.get(config.provider) if config else None
+        )
+
+        if config and endpoint:
+            base_url, api_key = endpoint
+            async for chunk in stream_openai_compatible(
+                base_url,
+                api_key,
+                config.name.split("/", 1)[-1],
+                prompt,
+                temperature=temperature,
+                max_tokens=max_tokens,
+            ):
+                yield chunk
+            return
+
+        llm = self.get_model(
+            model_name=model_name, temperature=temperature, max_tokens=max_tokens
+        )
+        async for piece in llm.astream(prompt):
+            extras = getattr(piece, "additional_kwargs", None) or {}
+            yield StreamChunk(
+                content=str(piece.content),
+                reasoning=extras.get("reasoning_content") or "",
+            )
 
     def get_cost_summary(self) -> dict[str, Any]:
         """Get cost summary for current session."""
@@ -461,22 +1043,4 @@ class ModelRouter:
         Returns:
             Model name
         """
-        # Task-based routing
-        routing = {
-            "main_agent": ModelTier.MEDIUM,  # gpt-4o, sonnet
-            "sub_agent": ModelTier.SMALL,  # gpt-4o-mini, haiku
-            "simple_query": ModelTier.SMALL,
-            "complex_reasoning": ModelTier.LARGE,  # opus, gpt-4
-            "code_generation": ModelTier.MEDIUM,
-            "code_review": ModelTier.SMALL,
-        }
-
-        desired_tier = routing.get(task_type, ModelTier.MEDIUM)
-
-        # Find first available model of desired tier
-        for name, config in SUPPORTED_MODELS.items():
-            if config.tier == desired_tier:
-                return name
-
-        # Fallback to default
-        return self.default_model
+        return model_for_task(task_type, default_model=self.default_model)
@@ -0,0 +1,241 @@
+"""Provider streaming-protocol parsing.
+
+LangChain handles streaming for the managed providers, but raw
+OpenAI-compatible endpoints (vLLM, custom gateways) and direct provider
+integrations need correct wire-level parsing: OpenAI/OpenRouter SSE
+``data:`` lines terminated by ``[DONE]``, Anthropic's event-typed SSE, and
+Gemini's chunked JSON. Everything normalizes into StreamChunk.
+"""
+
+import asyncio
+import json
+import urllib.request
+from collections.abc import AsyncIterator
+from typing import Any
+
+from pydantic import BaseModel
+
+
+class StreamChunk(BaseModel):
+    """A normalized streaming chunk from any provider.
+
+    Reasoning models (o1/o3, Claude extended thinking, Gemini thinking)
+    emit thinking content on a separate channel from the answer; it lands
+    in ``reasoning`` so renderers can keep the two apart. Reasoning tokens
+    are tracked separately because they're often priced differently.
+    """
+
+    content: str = ""
+    reasoning: str = ""
+    tokens_used: int | None = None
+    reasoning_tokens: int | None = None
+    # Prompt tokens served from the provider's cache (cost reporting)
+    cached_tokens: int | None = None
+    finish_reason: str | None = None
+    done: bool = False
+
+
+class SSEBuffer:
+    """Reassemble SSE events from arbitrary network read boundaries.
+
+    SSE frames are separated by blank lines, but reads can split a frame
+    anywhere - partial frames are buffered, never dropped. feed() returns
+    only the events completed by the latest read.
+    """
+
+    def __init__(self) -> None:
+        self._buffer = ""
+
+    def feed(self, data: str) -> list[tuple[str | None, str]]:
+        """Add raw data, returning completed (event_type, payload) pairs."""
+        self._buffer += data.replace("\r\n", "\n")
+        events: list[tuple[str | None, str]] = []
+
+        while "\n\n" in self._buffer:
+            frame, self._buffer = self._buffer.split("\n\n", 1)
+            event_type: str | None = None
+            data_lines: list[str] = []
+            for line in frame.splitlines():
+                if line.startswith("event:"):
+                    event_type = line[len("event:") :].strip()
+                elif line.startswith("data:"):
+                    data_lines.append(line[len("data:") :].lstrip())
+            if data_lines:
+                events.append((event_type, "\n".join(data_lines)))
+
+        return events
+
+
+def parse_openai_chunk(payload: str) -> StreamChunk | None:
+    """Parse an OpenAI/OpenRouter SSE data payload.
+
+    Returns None for unparseable payloads (they should be skipped, not
+    treated as stream corruption).
+    """
+    if payload.strip() == "[DONE]":
+        return StreamChunk(done=True)
+
+    try:
+        obj = json.loads(payload)
+    except ValueError:
+        return None
+
+    choices = obj.get("choices") or [{}]
+    choice = choices[0]
+    delta = choice.get("delta", {})
+    finish_reason = choice.get("finish_reason")
+    return StreamChunk(
+        content=delta.get("content") or "",
+        # "reasoning_content" (DeepSeek/vLLM) or "reasoning" (OpenRouter)
+        reasoning=delta.get("reasoning_content") or delta.get("reasoning") or "",
+        finish_reason=finish_reason,
+        tokens_used=_usage_total(obj.get("usage")),
+        reasoning_tokens=_usage_reasoning(obj.get("usage")),
+        cached_tokens=_usage_cached(obj.get("usage")),
+        done=finish_reason is not None,
+    )
+
+
+def parse_anthropic_event(event_type: str | None, payload: str) -> StreamChunk | None:
+    """Parse an Anthropic event-typed SSE payload."""
+    try:
+        obj = json.loads(payload)
+    except ValueError:
+        return None
+
+    kind = event_type or obj.get("type")
+    if kind == "content_block_delta":
+        delta = obj.get("delta", {})
+        if delta.get("type") == "thinking_delta":
+            return StreamChunk(reasoning=delta.get("thinking") or "")
+        return StreamChunk(content=delta.get("text") or "")
+    if kind == "message_delta":
+        usage = obj.get("usage", {})
+        return StreamChunk(
+            finish_reason=obj.get("delta", {}).get("stop_reason"),
+            tokens_used=usage.get("output_tokens"),
+        )
+    if kind == "message_start":
+        # Carries no content, but reports prompt-cache hits up front
+        cached = obj.get("message", {}).get("usage", {}).get(
+            "cache_read_input_tokens"
+        )
+        return StreamChunk(cached_tokens=cached) if cached else None
+    if kind == "message_stop":
+        return StreamChunk(done=True)
+    # ping, content_block_start/stop carry no content
+    return None
+
+
+def parse_gemini_chunk(payload: str) -> StreamChunk | None:
+    """Parse a Gemini chunked-JSON streaming payload."""
+    try:
+        obj = json.loads(payload)
+    except ValueError:
+        return None
+
+    candidates = obj.get("candidates") or [{}]
+    candidate = candidates[0]
+    parts = candidate.get("content", {}).get("parts", [])
+    # Thinking parts are flagged with "thought"; keep them off the answer
+    content = "".join(p.get("text", "") for p in parts if not p.get("thought"))
+    reasoning = "".join(p.get("text", "") for p in parts if p.get("thought"))
+    finish_reason = candidate.get("finishReason")
+    usage = obj.get("usageMetadata", {})
+    return StreamChunk(
+        content=content,
+        reasoning=reasoning,
+        finish_reason=finish_reason,
+        tokens_used=usage.get("totalTokenCount"),
+        reasoning_tokens=usage.get("thoughtsTokenCount"),
+        done=finish_reason is not None,
+    )
+
+
+async def stream_openai_compatible(
+    base_url: str,
+    api_key: str,
+    model: str,
+    prompt: str,
+    temperature: float = 0.3,
+    max_tokens: int | None = None,
+    timeout: float = 120.0,
+) -> AsyncIterator[StreamChunk]:
+    """Stream a chat completion from a raw OpenAI-compatible endpoint.
+
+    Talks to ``{base_url}/chat/completions`` directly (vLLM, Ollama,
+    custom gateways), reassembling SSE frames with SSEBuffer and
+    normalizing each ``data:`` payload through parse_openai_chunk.
+    """
+    body: dict[str, Any] = {
+        "model": model,
+        "messages": [{"role": "user", "content": prompt}],
+        "temperature": temperature,
+        "stream": True,
+        # Ask for a final usage frame so costs can be tracked
+        "stream_options": {"include_usage": True},
+    }
+    if max_tokens is not None:
+        body["max_tokens"] = max_tokens
+
+    request = urllib.request.Request(
+        f"{base_url.rstrip('/')}/chat/completions",
+        data=json.dumps(body).encode("utf-8"),
+        headers={
+            "Content-Type": "application/json",
+            "Authorization": f"Bearer {api_key}",
+        },
+        method="POST",
+    )
+
+    loop = asyncio.get_running_loop()
+    queue: asyncio.Queue[str | None] = asyncio.Queue()
+
+    def _read() -> None:
+        # Blocking reads happen in the executor thread; decoded text is
+        # handed back to the event loop as it arrives
+        with urllib.request.urlopen(request, timeout=timeout) as response:
+            while data := response.read(4096):
+                loop.call_soon_threadsafe(
+                    queue.put_nowait, data.decode("utf-8", errors="replace")
+                )
+
+    reader = loop.run_in_executor(None, _read)
+    reader.add_done_callback(
+        lambda _: loop.call_soon_threadsafe(queue.put_nowait, None)
+    )
+
+    buffer = SSEBuffer()
+    while (data := await queue.get()) is not None:
+        for _event_type, payload in buffer.feed(data):
+            chunk = parse_openai_chunk(payload)
+            if chunk is not None:
+                yield chunk
+                # Stop only on the [DONE] sentinel (no finish_reason): the
+                # usage frame requested via stream_options arrives after
+                # the finish_reason chunk
+                if chunk.done and chunk.finish_reason is None:
+                    return
+    # Stream ended without a [DONE]; surface connection/HTTP errors
+    await reader
+
+
+def _usage_total(usage: dict[str, Any] | None) -> int | None:
+    """Extract a total token count from an OpenAI-style usage block."""
+    if not usage:
+        return None
+    return usage.get("total_tokens")
+
+
+def _usage_reasoning(usage: dict[str, Any] | None) -> int | None:
+    """Extract the reasoning token count from an OpenAI-style usage block."""
+    if not usage:
+        return None
+    return usage.get("completion_tokens_details", {}).get("reasoning_tokens")
+
+
+def _usage_cached(usage: dict[str, Any] | None) -> int | None:
+    """Extract the cached prompt token count from an OpenAI-style usage block."""
+    if not usage:
+        return None
+    return usage.get("prompt_tokens_details", {}).get("cached_tokens")
@@ -19,6 +19,7 @@ class ModeCapabilities(BaseModel):
     can_read_files: bool = True
     can_write_files: bool = False
     can_execute_commands: bool = False
+    can_access_network: bool = False
     can_spawn_subagents: bool = False
     requires_confirmation: bool = False
 
@@ -36,6 +37,7 @@ MODE_CAPABILITIES = {
         can_read_files=True,
         can_write_files=True,
         can_execute_commands=False,
+        can_access_network=True,
         can_spawn_subagents=False,
         requires_confirmation=True,
     ),
@@ -43,6 +45,7 @@ MODE_CAPABILITIES = {
         can_read_files=True,
         can_write_files=True,
         can_execute_commands=True,
+        can_access_network=True,
         can_spawn_subagents=True,
         requires_confirmation=False,
     ),
@@ -0,0 +1,92 @@
+"""Approved-command permission rules for Aircher.
+
+Tracks which command patterns the user has approved for execution, either
+for the current session only or persistently. Patterns use shell-style
+globs (``git *``, ``npm run *``) matched against the full command line.
+"""
+
+import json
+from datetime import datetime
+from fnmatch import fnmatch
+from pathlib import Path
+
+from loguru import logger
+from pydantic import BaseModel, Field
+
+
+class PermissionRule(BaseModel):
+    """A single approved command pattern."""
+
+    pattern: str
+    scope: str = "session"  # "session" or "always"
+    created_at: datetime = Field(default_factory=datetime.now)
+
+
+class PermissionsManager:
+    """Store and match approved command patterns."""
+
+    def __init__(self, rules_path: Path | None = None):
+        if rules_path is None:
+            rules_path = Path.home() / ".aircher" / "permissions.json"
+
+        self.rules_path = rules_path
+        self.session_rules: list[PermissionRule] = []
+        self.persistent_rules: list[PermissionRule] = self._load()
+
+    def _load(self) -> list[PermissionRule]:
+        """Load persistent rules from disk."""
+        if not self.rules_path.exists():
+            return []
+        try:
+            data = json.loads(self.rules_path.read_text())
+            return [PermissionRule(**rule) for rule in data]
+        except (OSError, ValueError) as e:
+            logger.warning(f"Could not load permission rules: {e}")
+            return []
+
+    def _save(self) -> None:
+        """Persist the always-scoped rules to disk."""
+        try:
+            self.rules_path.parent.mkdir(parents=True, exist_ok=True)
+            self.rules_path.write_text(
+                json.dumps(
+                    [rule.model_dump(mode="json") for rule in self.persistent_rules],
+                    indent=2,
+                )
+            )
+        except OSError as e:
+            logger.warning(f"Could not save permission rules: {e}")
+
+    def approve(self, pattern: str, scope: str = "session") -> PermissionRule:
+        """Approve a command pattern for this session or permanently."""
+        rule = PermissionRule(pattern=pattern, scope=scope)
+        if scope == "always":
+            self.persistent_rules.append(rule)
+            self._save()
+        else:
+            self.session_rules.append(rule)
+        return rule
+
+    def is_approved(self, command: str) -> bool:
+        """Check whether a command matches any approved pattern."""
+        return any(
+            fnmatch(command, rule.pattern) for rule in self.list_rules()
+        )
+
+    def list_rules(self) -> list[PermissionRule]:
+        """All active rules, persistent first."""
+        return [*self.persistent_rules, *self.session_rules]
+
+    def remove(self, pattern: str) -> bool:
+        """Remove a rule by exact pattern. Returns True if one was removed."""
+        for rules, persist in (
+            (self.persistent_rules, True),
+            (self.session_rules, False),
+        ):
+            for rule in rules:
+                if rule.pattern == pattern:
+                    rules.remove(rule)
+                    if persist:
+                        self._save()
+                    return True
+        return False
@@ -0,0 +1,338 @@
+"""Project-level context readers for Aircher."""
+
+import json
+import subprocess
+import time
+from datetime import datetime
+from pathlib import Path
+
+from loguru import logger
+from pydantic import BaseModel, Field
+
+
+class ProjectNote(BaseModel):
+    """A durable fact remembered about the project."""
+
+    note: str
+    created_at: datetime = Field(default_factory=datetime.now)
+
+
+class ProjectNotes:
+    """Agent/user-accumulated memory persisted per project.
+
+    Distinct from hand-edited instruction files: notes are added via
+    /remember during sessions ("the API uses snake_case") and injected
+    into the system prompt, capped so they can't crowd out real context.
+    """
+
+    def __init__(self, project_dir: Path | None = None):
+        project_dir = project_dir or Path.cwd()
+        self.notes_path = project_dir / ".aircher" / "notes.json"
+        self.notes: list[ProjectNote] = self._load()
+
+    def _load(self) -> list[ProjectNote]:
+        """Load notes, tolerating absence and corruption."""
+        if not self.notes_path.exists():
+            return []
+        try:
+            data = json.loads(self.notes_path.read_text())
+            return [ProjectNote(**note) for note in data]
+        except (OSError, ValueError) as e:
+            logger.warning(f"Could not load project notes: {e}")
+            return []
+
+    def _save(self) -> None:
+        """Persist notes to the project dir."""
+        try:
+            self.notes_path.parent.mkdir(parents=True, exist_ok=True)
+            self.notes_path.write_text(
+                json.dumps(
+                    [note.model_dump(mode="json") for note in self.notes], indent=2
+                )
+            )
+        except OSError as e:
+            logger.warning(f"Could not save project notes: {e}")
+
+    def add(self, note: str) -> None:
+        """Remember a new note."""
+        self.notes.append(ProjectNote(note=note))
+        self._save()
+
+    def remove(self, index: int) -> bool:
+        """Remove a note by 0-based index. Returns True if removed."""
+        if 0 <= index < len(self.notes):
+            del self.notes[index]
+            self._save()
+            return True
+        return False
+
+    def render(self, max_chars: int = 2000) -> str:
+        """Render notes for system-prompt injection, newest-last, capped.
+
+        When over the cap, the oldest notes drop out first.
+        """
+        if not self.notes:
+            return ""
+
+        lines: list[str] = []
+        used = 0
+        for note in reversed(self.notes):
+            line = f"- {note.note}"
+            if used + len(line) + 1 > max_chars:
+                break
+            lines.append(line)
+            used += len(line) + 1
+        lines.reverse()
+        return "Project notes (accumulated memory):\n" + "\n".join(lines)
+
+
+# Agent-instruction files in precedence order; the first present wins.
+# AGENT.md is ours; the rest come from other tools and are honored so
+# users migrating don't have to re-author their instructions.
+INSTRUCTION_FILES = [
+    "AGENT.md",
+    "AGENTS.md",
+    "CLAUDE.md",
+    ".cursorrules",
+    ".github/copilot-instructions.md",
+]
+
+
+def read_agent_instructions(
+    project_dir: Path | None = None,
+) -> tuple[str, str] | None:
+    """Load the project's agent-instruction file for the system prompt.
+
+    Checks INSTRUCTION_FILES in order and returns (filename, content)
+    for the first non-empty one - files are never merged, so a project
+    adding AGENT.md cleanly overrides its older tool-specific files.
+    Oversized files are middle-elided via the context read helper.
+    Returns None when no instruction file exists.
+    """
+    from ..context import read_for_context
+
+    project_dir = project_dir or Path.cwd()
+    for name in INSTRUCTION_FILES:
+        path = project_dir / name
+        if not path.is_file():
+            continue
+        try:
+            content, _elided = read_for_context(path)
+        except OSError as e:
+            logger.warning(f"Could not read {name}: {e}")
+            continue
+        if content.strip():
+            logger.debug(f"Loaded agent instructions from {name}")
+            return name, content.strip()
+    return None
+
+
+class GitStatus(BaseModel):
+    """Snapshot of the project's git state."""
+
+    branch: str
+    dirty: bool
+    changed_files: list[str]
+
+    def summary(self) -> str:
+        """One-paragraph summary for the system prompt."""
+        if not self.dirty:
+            return f"Git: on branch {self.branch}, working tree clean."
+
+        shown = self.changed_files[:10]
+        listing = ", ".join(shown)
+        if len(self.changed_files) > len(shown):
+            listing += f", ... ({len(self.changed_files)} files total)"
+        return (
+            f"Git: on branch {self.branch} with uncommitted changes: {listing}."
+        )
+
+
+def _run_git(project_dir: Path, *args: str) -> str | None:
+    """Run a git command, returning stdout or None on any failure."""
+    try:
+        result = subprocess.run(
+            ["git", "-C", str(project_dir), *args],
+            capture_output=True,
+            text=True,
+            timeout=5,
+        )
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    if result.returncode != 0:
+        return None
+    return result.stdout
+
+
+# Extensions considered when guessing a project's primary language
+_LANGUAGE_EXTENSIONS = {
+    ".py": "python",
+    ".rs": "rust",
+    ".js": "javascript",
+    ".ts": "typescript",
+    ".go": "go",
+    ".java": "java",
+    ".cpp": "cpp",
+    ".c": "c",
+    ".rb": "ruby",
+    ".swift": "swift",
+    ".kt": "kotlin",
+}
+
+# Root manifest files that identify a language without sampling
+_MARKER_FILES = {
+    "Cargo.toml": "rust",
+    "go.mod": "go",
+    "package.json": "javascript",
+    "tsconfig.json": "typescript",
+    "pyproject.toml": "python",
+    "setup.py": "python",
+    "pom.xml": "java",
+    "build.gradle": "java",
+    "Gemfile": "ruby",
+    "Package.swift": "swift",
+}
+
+# Share of recognized files a language needs to count as primary
+_POLYGLOT_SHARE = 0.25
+
+
+def _extension_counts(project_dir: Path, sample_limit: int) -> dict[str, int]:
+    """Count recognized extensions across the tree, capped at sample_limit.
+
+    Hidden directories (.git, .venv) are skipped.
+    """
+    counts: dict[str, int] = {}
+    sampled = 0
+    for path in project_dir.rglob("*"):
+        relative = path.relative_to(project_dir)
+        if any(part.startswith(".") for part in relative.parts):
+            continue
+        language = _LANGUAGE_EXTENSIONS.get(path.suffix)
+        if language is None:
+            continue
+        counts[language] = counts.get(language, 0) + 1
+        sampled += 1
+        if sampled >= sample_limit:
+            break
+    return counts
+
+
+def detect_primary_language(
+    project_dir: Path | None = None, sample_limit: int = 500
+) -> str | None:
+    """Guess the project's primary language from file extensions.
+
+    Sampling is capped so huge repos don't stall startup. When no
+    recognized source files exist, a root marker file (pyproject.toml,
+    Cargo.toml, ...) breaks the tie. Returns None when nothing
+    recognizable is found.
+    """
+    project_dir = project_dir or Path.cwd()
+    counts = _extension_counts(project_dir, sample_limit)
+    if counts:
+        return max(counts, key=lambda lang: counts[lang])
+    for marker, language in _MARKER_FILES.items():
+        if (project_dir / marker).exists():
+            return language
+    return None
+
+
+def detect_languages(
+    project_dir: Path | None = None, sample_limit: int = 500
+) -> set[str]:
+    """Detect all of a project's primary languages.
+
+    Root marker files each contribute their language; extension sampling
+    adds every language holding at least a quarter of recognized files.
+    The union handles polyglot repos (Cargo.toml plus a TypeScript
+    frontend) and manifest-less directories of scripts alike. Empty set
+    when nothing is recognizable.
+    """
+    project_dir = project_dir or Path.cwd()
+    languages = {
+        language
+        for marker, language in _MARKER_FILES.items()
+        if (project_dir / marker).exists()
+    }
+    counts = _extension_counts(project_dir, sample_limit)
+    total = sum(counts.values())
+    if total:
+        languages.update(
+            language
+            for language, count in counts.items()
+            if count / total >= _POLYGLOT_SHARE
+        )
+    return languages
+
+
+def changed_files(project_dir: Path, ref: str = "HEAD") -> list[str] | None:
+    """List paths changed relative to a ref, plus untracked files.
+
+    Deleted files are included (their paths appear in the diff but no
+    longer exist on disk), so callers can purge them. Returns None in
+    non-git directories or when git is unavailable.
+    """
+    diff = _run_git(project_dir, "diff", "--name-only", ref)
+    if diff is None:
+        return None
+    untracked = _run_git(project_dir, "ls-files", "--others", "--exclude-standard")
+
+    names = [line for line in diff.splitlines() if line.strip()]
+    if untracked:
+        names.extend(line for line in untracked.splitlines() if line.strip())
+    # Preserve order, drop duplicates
+    return list(dict.fromkeys(names))
+
+
+def read_git_status(project_dir: Path) -> GitStatus | None:
+    """Read the current git branch and working-tree state.
+
+    Returns None in non-git directories or when git is unavailable - callers
+    should degrade silently.
+    """
+    branch = _run_git(project_dir, "rev-parse", "--abbrev-ref", "HEAD")
+    if branch is None:
+        return None
+
+    porcelain = _run_git(project_dir, "status", "--porcelain")
+    if porcelain is None:
+        return None
+
+    changed_files = [
+        line[3:].strip() for line in porcelain.splitlines() if line.strip()
+    ]
+    return GitStatus(
+        branch=branch.strip(),
+        dirty=bool(changed_files),
+        changed_files=changed_files,
+    )
+
+
+class GitStatusCache:
+    """Time-based cache around read_git_status.
+
+    Git state changes rarely within a single exchange; the TTL keeps repeated
+    prompt builds from shelling out on every request. A file-monitor can call
+    invalidate() to force a refresh.
+    """
+
+    def __init__(self, project_dir: Path, ttl_seconds: float = 10.0):
+        self.project_dir = project_dir
+        self.ttl_seconds = ttl_seconds
+        self._cached: GitStatus | None = None
+        self._read_at: float | None = None
+
+    def get(self) -> GitStatus | None:
+        """Get the (possibly cached) git status."""
+        now = time.monotonic()
+        if self._read_at is None or now - self._read_at > self.ttl_seconds:
+            self._cached = read_git_status(self.project_dir)
+            self._read_at = now
+            if self._cached:
+                logger.debug(f"Git status refreshed: {self._cached.summary()}")
+        return self._cached
+
+    def invalidate(self) -> None:
+        """Force a refresh on the next get() (file-monitor hook)."""
+        self._read_at = None
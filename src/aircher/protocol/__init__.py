@@ -151,6 +151,7 @@ class ACPSession:
     last_activity: datetime
     mode: AgentMode
     user_id: str | None = None
+    project: str | None = None
     metadata: dict[str, Any] | None = None
 
     def __post_init__(self):
@@ -0,0 +1,136 @@
+"""Outgoing-message security checks for Aircher."""
+
+import math
+import re
+from typing import Any
+
+from pydantic import BaseModel
+
+
+class SecretMatch(BaseModel):
+    """A suspected secret found in outgoing text."""
+
+    pattern_name: str
+    matched_text: str
+    start: int
+    end: int
+
+    def masked(self) -> str:
+        """Return the matched text with all but the edges masked."""
+        text = self.matched_text
+        if len(text) <= 8:
+            return "*" * len(text)
+        return f"{text[:4]}{'*' * (len(text) - 8)}{text[-4:]}"
+
+
+# Common secret formats. Patterns favor precision over recall - false
+# positives train users to ignore the warning.
+DEFAULT_SECRET_PATTERNS: dict[str, str] = {
+    "aws_access_key": r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    "aws_secret_key": r"\baws_secret_access_key\s*[=:]\s*\S{40}\b",
+    "private_key_header": r"-----BEGIN (?:RSA |EC |DSA |OPENSSH )?PRIVATE KEY-----",
+    "github_token": r"\b(?:ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{36,}\b",
+    "openai_api_key": r"\bsk-[A-Za-z0-9_-]{20,}\b",
+    "anthropic_api_key": r"\bsk-ant-[A-Za-z0-9_-]{20,}\b",
+    "slack_token": r"\bxox[baprs]-[A-Za-z0-9-]{10,}\b",
+    "generic_password": r"(?i)\bpassword\s*[=:]\s*\S{8,}",
+}
+
+
+class SecretScanner:
+    """Scan outgoing messages for accidental secrets before they reach a provider."""
+
+    def __init__(
+        self,
+        extra_patterns: dict[str, str] | None = None,
+        entropy_threshold: float = 4.5,
+        min_entropy_length: int = 32,
+    ):
+        """Initialize the scanner.
+
+        Args:
+            extra_patterns: Additional named regex patterns from config.
+            entropy_threshold: Shannon entropy (bits/char) above which a long
+                token is flagged as a probable secret.
+            min_entropy_length: Minimum token length for the entropy check.
+        """
+        patterns = dict(DEFAULT_SECRET_PATTERNS)
+        if extra_patterns:
+            patterns.update(extra_patterns)
+
+        self.patterns = {
+            name: re.compile(pattern) for name, pattern in patterns.items()
+        }
+        self.entropy_threshold = entropy_threshold
+        self.min_entropy_length = min_entropy_length
+
+    def scan(self, text: str) -> list[SecretMatch]:
+        """Scan text and return all suspected secrets."""
+        matches: list[SecretMatch] = []
+
+        for name, pattern in self.patterns.items():
+            for match in pattern.finditer(text):
+                matches.append(
+                    SecretMatch(
+                        pattern_name=name,
+                        matched_text=match.group(0),
+                        start=match.start(),
+                        end=match.end(),
+                    )
+                )
+
+        matches.extend(self._scan_high_entropy(text, matches))
+
+        # Stable order for display
+        matches.sort(key=lambda m: m.start)
+        return matches
+
+    def _scan_high_entropy(
+        self, text: str, existing: list[SecretMatch]
+    ) -> list[SecretMatch]:
+        """Flag long high-entropy tokens not already caught by a pattern."""
+        covered = [(m.start, m.end) for m in existing]
+        matches: list[SecretMatch] = []
+
+        for match in re.finditer(r"\b[A-Za-z0-9+/_=-]{%d,}\b" % self.min_entropy_length, text):
+            if any(match.start() < end and match.end() > start for start, end in covered):
+                continue
+
+            token = match.group(0)
+            if self._shannon_entropy(token) >= self.entropy_threshold:
+                matches.append(
+                    SecretMatch(
+                        pattern_name="high_entropy_token",
+                        matched_text=token,
+                        start=match.start(),
+                        end=match.end(),
+                    )
+                )
+
+        return matches
+
+    @staticmethod
+    def _shannon_entropy(text: str) -> float:
+        """Calculate Shannon entropy in bits per character."""
+        if not text:
+            return 0.0
+
+        entropy = 0.0
+        for char in set(text):
+            probability = text.count(char) / len(text)
+            entropy -= probability * math.log2(probability)
+        return entropy
+
+    def mask_text(self, text: str, matches: list[SecretMatch]) -> str:
+        """Return text with suspected secrets masked for safe preview."""
+        # Replace from the end so earlier offsets stay valid
+        masked = text
+        for match in sorted(matches, key=lambda m: m.start, reverse=True):
+            masked = masked[: match.start] + match.masked() + masked[match.end :]
+        return masked
+
+    def summarize(self, matches: list[SecretMatch]) -> list[dict[str, Any]]:
+        """Summarize matches for display (pattern name plus masked value)."""
+        return [
+            {"pattern": m.pattern_name, "preview": m.masked()} for m in matches
+        ]
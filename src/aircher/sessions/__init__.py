@@ -2,6 +2,8 @@
 
 import json
 import sqlite3
+import uuid
+from contextlib import contextmanager
 from datetime import datetime
 from pathlib import Path
 from typing import Any, Dict, List, Optional
@@ -10,6 +12,22 @@ from loguru import logger
 
 from ..protocol import ACPMessage, ACPSession, AgentMode
 
+# Bump whenever _MIGRATIONS gains an entry
+SCHEMA_VERSION = 2
+
+
+def _migrate_v2_add_project(conn: sqlite3.Connection) -> None:
+    """v2: track which project a session belongs to (for /resume)."""
+    cursor = conn.execute("PRAGMA table_info(sessions)")
+    columns = {row[1] for row in cursor.fetchall()}
+    if "project" not in columns:
+        conn.execute("ALTER TABLE sessions ADD COLUMN project TEXT")
+
+
+# Ordered (target_version, migration) pairs; each runs when upgrading a
+# database whose stamped version is below the target
+_MIGRATIONS = [(2, _migrate_v2_add_project)]
+
 
 class SessionStorage:
     """SQLite-based session storage."""
@@ -19,13 +37,68 @@ class SessionStorage:
             db_path = Path.home() / ".aircher" / "sessions.db"
 
         self.db_path = db_path
-        self.db_path.parent.mkdir(parents=True, exist_ok=True)
 
-        self._init_database()
+        # On read-only filesystems fall back to an ephemeral in-memory
+        # database so the app still runs; sessions just won't persist
+        self._memory_conn: sqlite3.Connection | None = None
+        try:
+            self.db_path.parent.mkdir(parents=True, exist_ok=True)
+            self._open_database()
+        except (OSError, sqlite3.Error) as e:
+            logger.warning(
+                f"Session database at {self.db_path} is unavailable ({e}); "
+                "using ephemeral in-memory sessions"
+            )
+            self._memory_conn = sqlite3.connect(":memory:")
+            self._init_database()
+
+    def _open_database(self) -> None:
+        """Initialize the on-disk database, recovering from corruption.
+
+        An unreadable or failing-integrity database is moved aside to a
+        timestamped backup (nothing is deleted) and a fresh one is
+        created, so startup never blocks on an opaque SQLite error.
+        """
+        try:
+            self._init_database()
+        except sqlite3.DatabaseError as e:
+            backup = self.db_path.with_suffix(
+                f".corrupt-{datetime.now().strftime('%Y%m%d_%H%M%S')}"
+            )
+            logger.error(
+                f"Session database looks corrupt or incompatible ({e}); "
+                f"backing it up to {backup} and reinitializing"
+            )
+            self.db_path.rename(backup)
+            self._init_database()
+
+    @contextmanager
+    def _connect(self):
+        """Yield a database connection (shared one in ephemeral mode)."""
+        if self._memory_conn is not None:
+            yield self._memory_conn
+        else:
+            conn = sqlite3.connect(self.db_path)
+            try:
+                yield conn
+            finally:
+                conn.close()
 
     def _init_database(self):
-        """Initialize database tables."""
-        with sqlite3.connect(self.db_path) as conn:
+        """Initialize database tables, migrating older schemas forward."""
+        with self._connect() as conn:
+            self._check_integrity(conn)
+
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)"
+            )
+            version = self._schema_version(conn)
+            if version > SCHEMA_VERSION:
+                raise sqlite3.DatabaseError(
+                    f"schema v{version} is newer than this build supports "
+                    f"(v{SCHEMA_VERSION}); upgrade aircher or restore a backup"
+                )
+
             conn.execute("""
                 CREATE TABLE IF NOT EXISTS sessions (
                     id TEXT PRIMARY KEY,
@@ -33,10 +106,21 @@ class SessionStorage:
                     last_activity TIMESTAMP NOT NULL,
                     mode TEXT NOT NULL,
                     user_id TEXT,
+                    project TEXT,
                     metadata TEXT
                 )
             """)
 
+            for target, migrate in _MIGRATIONS:
+                if version and version < target:
+                    logger.info(f"Migrating session database to schema v{target}")
+                    migrate(conn)
+
+            conn.execute("DELETE FROM schema_version")
+            conn.execute(
+                "INSERT INTO schema_version (version) VALUES (?)", (SCHEMA_VERSION,)
+            )
+
             conn.execute("""
                 CREATE TABLE IF NOT EXISTS messages (
                     id TEXT PRIMARY KEY,
@@ -73,17 +157,43 @@ class SessionStorage:
             conn.execute(
                 "CREATE INDEX IF NOT EXISTS idx_tool_calls_session ON tool_calls (session_id)"
             )
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_sessions_project ON sessions (project, last_activity)"
+            )
 
             conn.commit()
 
+    @staticmethod
+    def _check_integrity(conn: sqlite3.Connection) -> None:
+        """Raise a DatabaseError when the file fails SQLite's own check."""
+        row = conn.execute("PRAGMA integrity_check").fetchone()
+        if row is None or row[0] != "ok":
+            raise sqlite3.DatabaseError(
+                f"integrity check failed: {row[0] if row else 'no result'}"
+            )
+
+    @staticmethod
+    def _schema_version(conn: sqlite3.Connection) -> int:
+        """Read the stamped schema version (inferred for older databases)."""
+        row = conn.execute("SELECT version FROM schema_version").fetchone()
+        if row:
+            return int(row[0])
+        # Pre-versioning databases: infer from the sessions table shape;
+        # 0 means a fresh database that gets the latest schema directly
+        cursor = conn.execute("PRAGMA table_info(sessions)")
+        columns = {r[1] for r in cursor.fetchall()}
+        if not columns:
+            return 0
+        return 2 if "project" in columns else 1
+
     def create_session(self, session: ACPSession) -> bool:
         """Create a new session."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.execute(
                     """
-                    INSERT INTO sessions (id, created_at, last_activity, mode, user_id, metadata)
-                    VALUES (?, ?, ?, ?, ?, ?)
+                    INSERT INTO sessions (id, created_at, last_activity, mode, user_id, project, metadata)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)
                     """,
                     (
                         session.id,
@@ -91,6 +201,7 @@ class SessionStorage:
                         session.last_activity.isoformat(),
                         session.mode.value,
                         session.user_id,
+                        session.project,
                         json.dumps(session.metadata) if session.metadata else None,
                     ),
                 )
@@ -103,7 +214,7 @@ class SessionStorage:
     def get_session(self, session_id: str) -> ACPSession | None:
         """Get session by ID."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.row_factory = sqlite3.Row
                 cursor = conn.execute(
                     "SELECT * FROM sessions WHERE id = ?", (session_id,)
@@ -111,14 +222,7 @@ class SessionStorage:
                 row = cursor.fetchone()
 
                 if row:
-                    return ACPSession(
-                        id=row["id"],
-                        created_at=datetime.fromisoformat(row["created_at"]),
-                        last_activity=datetime.fromisoformat(row["last_activity"]),
-                        mode=AgentMode(row["mode"]),
-                        user_id=row["user_id"],
-                        metadata=json.loads(row["metadata"]) if row["metadata"] else {},
-                    )
+                    return self._row_to_session(row)
                 return None
         except sqlite3.Error as e:
             logger.error(f"Failed to get session: {e}")
@@ -127,17 +231,18 @@ class SessionStorage:
     def update_session(self, session: ACPSession) -> bool:
         """Update session information."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.execute(
                     """
                     UPDATE sessions
-                    SET last_activity = ?, mode = ?, user_id = ?, metadata = ?
+                    SET last_activity = ?, mode = ?, user_id = ?, project = ?, metadata = ?
                     WHERE id = ?
                     """,
                     (
                         session.last_activity.isoformat(),
                         session.mode.value,
                         session.user_id,
+                        session.project,
                         json.dumps(session.metadata) if session.metadata else None,
                         session.id,
                     ),
@@ -151,7 +256,7 @@ class SessionStorage:
     def delete_session(self, session_id: str) -> bool:
         """Delete a session and all associated data."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.execute("DELETE FROM sessions WHERE id = ?", (session_id,))
                 conn.commit()
                 return True
@@ -159,11 +264,27 @@ class SessionStorage:
             logger.error(f"Failed to delete session: {e}")
             return False
 
-    def list_sessions(self, limit: int = 100, offset: int = 0) -> list[ACPSession]:
-        """List sessions ordered by last activity."""
+    def list_sessions(
+        self, limit: int = 100, offset: int = 0, tag: str | None = None
+    ) -> list[ACPSession]:
+        """List sessions ordered by last activity, optionally filtered by tag.
+
+        Tags live inside the metadata JSON, so the tag filter is applied in
+        Python after the query rather than in SQL.
+        """
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.row_factory = sqlite3.Row
+                if tag is not None:
+                    cursor = conn.execute(
+                        "SELECT * FROM sessions ORDER BY last_activity DESC"
+                    )
+                    sessions = [self._row_to_session(row) for row in cursor.fetchall()]
+                    matching = [
+                        s for s in sessions if tag in s.metadata.get("tags", [])
+                    ]
+                    return matching[offset : offset + limit]
+
                 cursor = conn.execute(
                     """
                     SELECT * FROM sessions
@@ -173,30 +294,67 @@ class SessionStorage:
                     (limit, offset),
                 )
 
-                sessions = []
-                for row in cursor.fetchall():
-                    sessions.append(
-                        ACPSession(
-                            id=row["id"],
-                            created_at=datetime.fromisoformat(row["created_at"]),
-                            last_activity=datetime.fromisoformat(row["last_activity"]),
-                            mode=AgentMode(row["mode"]),
-                            user_id=row["user_id"],
-                            metadata=json.loads(row["metadata"])
-                            if row["metadata"]
-                            else {},
-                        )
-                    )
-
-                return sessions
+                return [self._row_to_session(row) for row in cursor.fetchall()]
         except sqlite3.Error as e:
             logger.error(f"Failed to list sessions: {e}")
             return []
 
+    def most_recent_for_project(self, project: str) -> ACPSession | None:
+        """Get the most recently active session for a project."""
+        try:
+            with self._connect() as conn:
+                conn.row_factory = sqlite3.Row
+                cursor = conn.execute(
+                    """
+                    SELECT * FROM sessions
+                    WHERE project = ?
+                    ORDER BY last_activity DESC
+                    LIMIT 1
+                    """,
+                    (project,),
+                )
+                row = cursor.fetchone()
+                return self._row_to_session(row) if row else None
+        except sqlite3.Error as e:
+            logger.error(f"Failed to get most recent session: {e}")
+            return None
+
+    def recent_for_project(self, project: str, limit: int = 20) -> list[ACPSession]:
+        """Get the most recently active sessions for a project, newest first."""
+        try:
+            with self._connect() as conn:
+                conn.row_factory = sqlite3.Row
+                cursor = conn.execute(
+                    """
+                    SELECT * FROM sessions
+                    WHERE project = ?
+                    ORDER BY last_activity DESC
+                    LIMIT ?
+                    """,
+                    (project, limit),
+                )
+                return [self._row_to_session(row) for row in cursor.fetchall()]
+        except sqlite3.Error as e:
+            logger.error(f"Failed to list project sessions: {e}")
+            return []
+
+    @staticmethod
+    def _row_to_session(row: sqlite3.Row) -> ACPSession:
+        """Build an ACPSession from a sessions table row."""
+        return ACPSession(
+            id=row["id"],
+            created_at=datetime.fromisoformat(row["created_at"]),
+            last_activity=datetime.fromisoformat(row["last_activity"]),
+            mode=AgentMode(row["mode"]),
+            user_id=row["user_id"],
+            project=row["project"],
+            metadata=json.loads(row["metadata"]) if row["metadata"] else {},
+        )
+
     def store_message(self, message: ACPMessage) -> bool:
         """Store a message."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.execute(
                     """
                     INSERT INTO messages (id, session_id, type, timestamp, data)
@@ -216,12 +374,49 @@ class SessionStorage:
             logger.error(f"Failed to store message: {e}")
             return False
 
+    def store_chat_message(
+        self,
+        session_id: str,
+        role: str,
+        content: str,
+        metadata: dict[str, Any] | None = None,
+    ) -> bool:
+        """Store a TUI/one-shot chat message (role + content)."""
+        try:
+            with self._connect() as conn:
+                timestamp = datetime.now()
+                conn.execute(
+                    """
+                    INSERT INTO messages (id, session_id, type, timestamp, data)
+                    VALUES (?, ?, ?, ?, ?)
+                    """,
+                    (
+                        str(uuid.uuid4()),
+                        session_id,
+                        role,
+                        timestamp.isoformat(),
+                        json.dumps(
+                            {
+                                "role": role,
+                                "content": content,
+                                "timestamp": timestamp.isoformat(),
+                                "metadata": metadata or {},
+                            }
+                        ),
+                    ),
+                )
+                conn.commit()
+                return True
+        except sqlite3.Error as e:
+            logger.error(f"Failed to store chat message: {e}")
+            return False
+
     def get_messages(
         self, session_id: str, limit: int = 100, offset: int = 0
     ) -> list[dict[str, Any]]:
         """Get messages for a session."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 conn.row_factory = sqlite3.Row
                 cursor = conn.execute(
                     """
@@ -245,7 +440,7 @@ class SessionStorage:
     def cleanup_old_sessions(self, days: int = 30) -> int:
         """Clean up sessions older than specified days."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 cursor = conn.execute(
                     f"""
                     DELETE FROM sessions
@@ -263,7 +458,7 @@ class SessionStorage:
     def get_session_stats(self) -> dict[str, Any]:
         """Get session statistics."""
         try:
-            with sqlite3.connect(self.db_path) as conn:
+            with self._connect() as conn:
                 cursor = conn.execute("SELECT COUNT(*) as count FROM sessions")
                 total_sessions = cursor.fetchone()["count"]
 
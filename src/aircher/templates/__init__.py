@@ -0,0 +1,87 @@
+"""Named prompt templates for Aircher.
+
+Templates are reusable prompt scaffolds with placeholders:
+
+- ``{{cursor}}`` - where the user's fill-in text goes
+- ``{{file}}`` - replaced with a ``@file:<path>`` attachment for the
+  argument given at expansion time
+
+Project templates (``.aircher/templates.json``) shadow global ones
+(``~/.aircher/templates.json``) by name.
+"""
+
+import json
+from pathlib import Path
+
+from loguru import logger
+
+CURSOR_PLACEHOLDER = "{{cursor}}"
+FILE_PLACEHOLDER = "{{file}}"
+
+
+class TemplateStore:
+    """Load and persist named prompt templates."""
+
+    def __init__(
+        self,
+        project_dir: Path | None = None,
+        global_path: Path | None = None,
+    ):
+        project_dir = project_dir or Path.cwd()
+        self.project_path = project_dir / ".aircher" / "templates.json"
+        self.global_path = global_path or Path.home() / ".aircher" / "templates.json"
+
+    @staticmethod
+    def _load(path: Path) -> dict[str, str]:
+        """Load a template file, tolerating absence and corruption."""
+        if not path.exists():
+            return {}
+        try:
+            data = json.loads(path.read_text())
+            return {str(k): str(v) for k, v in data.items()}
+        except (OSError, ValueError) as e:
+            logger.warning(f"Could not load templates from {path}: {e}")
+            return {}
+
+    def list_templates(self) -> dict[str, str]:
+        """All templates, project entries shadowing global ones."""
+        return {**self._load(self.global_path), **self._load(self.project_path)}
+
+    def get(self, name: str) -> str | None:
+        """Get a template by name."""
+        return self.list_templates().get(name)
+
+    def add(self, name: str, content: str, scope: str = "project") -> None:
+        """Add (or replace) a template in the project or global store."""
+        path = self.project_path if scope == "project" else self.global_path
+        templates = self._load(path)
+        templates[name] = content
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(templates, indent=2))
+
+    def remove(self, name: str) -> bool:
+        """Remove a template by name. Returns True if one was removed."""
+        removed = False
+        for path in (self.project_path, self.global_path):
+            templates = self._load(path)
+            if name in templates:
+                del templates[name]
+                path.write_text(json.dumps(templates, indent=2))
+                removed = True
+        return removed
+
+
+def expand_template(template: str, file_arg: str | None = None) -> tuple[str, str]:
+    """Expand placeholders, returning (prefix, suffix) around the cursor.
+
+    The {{file}} placeholder becomes a @file: attachment marker when an
+    argument is given (and is dropped otherwise). The template splits at
+    {{cursor}} so the caller can collect the fill-in text in between; with
+    no cursor placeholder the whole template is the prefix.
+    """
+    if FILE_PLACEHOLDER in template:
+        replacement = f"@file:{file_arg}" if file_arg else ""
+        template = template.replace(FILE_PLACEHOLDER, replacement)
+
+    prefix, _, suffix = template.partition(CURSOR_PLACEHOLDER)
+    return prefix, suffix
@@ -6,6 +6,9 @@ from .base import BaseTool, ToolInput, ToolOutput
 # Import concrete tools
 from .bash import BashTool
 from .file_ops import ListDirectoryTool, ReadFileTool, SearchFilesTool, WriteFileTool
+from .git_ops import GitCommitTool
+from .scratchpad import ScratchpadTool, scratchpad_path
+from .web_search import WebSearchTool, web_search_available
 
 __all__ = [
     "BaseTool",
@@ -16,4 +19,9 @@ __all__ = [
     "WriteFileTool",
     "ListDirectoryTool",
     "SearchFilesTool",
+    "GitCommitTool",
+    "ScratchpadTool",
+    "scratchpad_path",
+    "WebSearchTool",
+    "web_search_available",
 ]
@@ -23,6 +23,11 @@ class BashResult:
 class BashTool:
     """Safe bash tool execution with fallbacks."""
 
+    # Not a BaseTool subclass (synchronous API, returns BashResult), but
+    # the agent's allow/denylist and capability filters key off tool.name
+    name = "bash"
+    description = "Execute shell commands"
+
     def __init__(self, tool_manager):
         self.tool_manager = tool_manager
         self.timeout = 30  # Default timeout in seconds
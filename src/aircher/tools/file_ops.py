@@ -1,13 +1,66 @@
 """File operation tools for Aircher."""
 
+import fnmatch
 import shutil
 from pathlib import Path
 from typing import Any
 
 from loguru import logger
 
+from ..context import DEFAULT_READ_TOKEN_BUDGET, read_for_context
 from .base import BaseTool, ToolInput, ToolOutput
 
+# Directories never worth listing, even without a .gitignore
+_ALWAYS_IGNORED = {".git", "__pycache__", "node_modules", "target", ".venv"}
+
+
+class IgnoreMatcher:
+    """Pragmatic .gitignore-style matching for directory listings.
+
+    Reads .gitignore and .aircherignore from the listing root and handles
+    the common cases: plain names, globs, dir-only (trailing /) and
+    root-anchored (leading /) patterns. Negation is out of scope; the
+    include_ignored flag is the escape hatch.
+    """
+
+    def __init__(self, root: Path):
+        # (pattern, dir_only, anchored)
+        self.patterns: list[tuple[str, bool, bool]] = []
+        for name in (".gitignore", ".aircherignore"):
+            try:
+                lines = (root / name).read_text().splitlines()
+            except OSError:
+                continue
+            for line in lines:
+                line = line.strip()
+                if not line or line.startswith(("#", "!")):
+                    continue
+                dir_only = line.endswith("/")
+                anchored = line.startswith("/")
+                pattern = line.strip("/")
+                if pattern:
+                    self.patterns.append((pattern, dir_only, anchored))
+
+    def is_ignored(self, rel_path: str, is_dir: bool) -> bool:
+        """Check a root-relative path (and its ancestors) against patterns."""
+        parts = rel_path.split("/")
+        if any(part in _ALWAYS_IGNORED for part in parts):
+            return True
+        for i in range(len(parts)):
+            candidate = "/".join(parts[: i + 1])
+            candidate_is_dir = is_dir or i < len(parts) - 1
+            for pattern, dir_only, anchored in self.patterns:
+                if dir_only and not candidate_is_dir:
+                    continue
+                if anchored:
+                    if fnmatch.fnmatch(candidate, pattern):
+                        return True
+                elif fnmatch.fnmatch(parts[i], pattern) or fnmatch.fnmatch(
+                    candidate, pattern
+                ):
+                    return True
+        return False
+
 
 class ReadFileTool(BaseTool):
     """Tool for reading file contents."""
@@ -64,25 +117,40 @@ class ReadFileTool(BaseTool):
             if not file_path.is_file():
                 return ToolOutput(success=False, error=f"Path is not a file: {path}")
 
+            # Whole-file reads go through the budget-aware helper so a huge
+            # file gets head/tail with elision instead of filling the
+            # context window; explicit offset/limit reads keep full control
+            explicit_range = "offset" in kwargs or "limit" in kwargs
+            elided = False
+            if not explicit_range:
+                content, elided = read_for_context(
+                    file_path, token_budget=DEFAULT_READ_TOKEN_BUDGET
+                )
+
             # Read file content
             with open(file_path, encoding="utf-8", errors="replace") as f:
                 lines = f.readlines()
 
-            # Apply offset and limit
-            selected_lines = lines[offset : offset + limit]
+            if explicit_range or not elided:
+                # Apply offset and limit
+                selected_lines = lines[offset : offset + limit]
 
-            # Add line numbers
-            numbered_lines = []
-            for i, line in enumerate(selected_lines, start=offset + 1):
-                numbered_lines.append(f"{i:6d}\t{line.rstrip()}")
+                # Add line numbers
+                numbered_lines = []
+                for i, line in enumerate(selected_lines, start=offset + 1):
+                    numbered_lines.append(f"{i:6d}\t{line.rstrip()}")
 
-            content = "\n".join(numbered_lines)
+                content = "\n".join(numbered_lines)
+                lines_returned = len(selected_lines)
+            else:
+                lines_returned = content.count("\n") + 1
 
             metadata = {
                 "total_lines": len(lines),
-                "lines_returned": len(selected_lines),
+                "lines_returned": lines_returned,
                 "offset": offset,
                 "limit": limit,
+                "elided": elided,
                 "file_size": file_path.stat().st_size,
                 "file_type": file_path.suffix,
             }
@@ -184,7 +252,9 @@ class ListDirectoryTool(BaseTool):
 
     def __init__(self):
         super().__init__(
-            name="list_directory", description="List contents of a directory"
+            name="list_directory",
+            description="List contents of a directory "
+            "(gitignored files are skipped by default)",
         )
 
     def get_input_schema(self) -> ToolInput:
@@ -215,6 +285,17 @@ class ListDirectoryTool(BaseTool):
                         "description": "Maximum depth for recursive listing",
                         "default": 3,
                     },
+                    "include_ignored": {
+                        "type": "boolean",
+                        "description": "Include files matched by .gitignore "
+                        "or .aircherignore",
+                        "default": False,
+                    },
+                    "glob": {
+                        "type": "string",
+                        "description": "Only list files matching this glob "
+                        "pattern (e.g. *.py)",
+                    },
                 },
                 "required": [],
             },
@@ -227,6 +308,8 @@ class ListDirectoryTool(BaseTool):
             show_hidden = kwargs.get("show_hidden", False)
             recursive = kwargs.get("recursive", False)
             max_depth = kwargs.get("max_depth", 3)
+            include_ignored = kwargs.get("include_ignored", False)
+            glob = kwargs.get("glob")
 
             dir_path = Path(path)
 
@@ -245,6 +328,8 @@ class ListDirectoryTool(BaseTool):
                     success=False, error=f"Path is not a directory: {path}"
                 )
 
+            matcher = None if include_ignored else IgnoreMatcher(dir_path)
+
             def list_dir(
                 directory: Path, current_depth: int = 0
             ) -> list[dict[str, Any]]:
@@ -257,6 +342,20 @@ class ListDirectoryTool(BaseTool):
                         if not show_hidden and item.name.startswith("."):
                             continue
 
+                        rel = item.relative_to(dir_path).as_posix()
+                        if matcher and matcher.is_ignored(rel, item.is_dir()):
+                            continue
+
+                        # Glob filters files only; directories stay
+                        # visible so recursion still reaches matches
+                        if (
+                            glob
+                            and not item.is_dir()
+                            and not fnmatch.fnmatch(item.name, glob)
+                            and not fnmatch.fnmatch(rel, glob)
+                        ):
+                            continue
+
                         stat = item.stat()
                         item_info = {
                             "name": item.name,
@@ -289,6 +388,7 @@ class ListDirectoryTool(BaseTool):
                 "directories": len([i for i in items if i["type"] == "directory"]),
                 "files": len([i for i in items if i["type"] == "file"]),
                 "path": str(dir_path.resolve()),
+                "ignores_respected": not include_ignored,
             }
 
             return ToolOutput(success=True, data=items, metadata=metadata)
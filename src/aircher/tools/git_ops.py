@@ -0,0 +1,116 @@
+"""Git operation tools."""
+
+import subprocess
+from pathlib import Path
+from typing import Any
+
+from loguru import logger
+
+from .base import BaseTool, ToolInput, ToolOutput
+
+
+def _run_git(*args: str, cwd: Path | None = None) -> subprocess.CompletedProcess:
+    """Run a git command, capturing output."""
+    return subprocess.run(
+        ["git", *args],
+        cwd=cwd or Path.cwd(),
+        capture_output=True,
+        text=True,
+        timeout=30,
+    )
+
+
+class GitCommitTool(BaseTool):
+    """Tool for staging files and creating a git commit.
+
+    Completes the edit->commit loop for autonomous workflows. Gated by
+    mode capabilities like write_file: unavailable in read mode and
+    subject to confirmation outside turbo mode.
+    """
+
+    def __init__(self):
+        super().__init__(
+            name="git_commit",
+            description="Stage the given files and create a git commit",
+        )
+
+    def get_input_schema(self) -> ToolInput:
+        """Get input schema for git commit tool."""
+        return ToolInput(
+            name=self.name,
+            description=self.description,
+            parameters={
+                "type": "object",
+                "properties": {
+                    "files": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Paths to stage for the commit",
+                    },
+                    "message": {
+                        "type": "string",
+                        "description": "Commit message describing the change",
+                    },
+                },
+                "required": ["files", "message"],
+            },
+        )
+
+    async def execute(self, **kwargs: Any) -> ToolOutput:
+        """Stage the files and commit them."""
+        try:
+            files = kwargs["files"]
+            message = kwargs["message"]
+
+            if not files:
+                return ToolOutput(success=False, error="No files to commit")
+            if not message.strip():
+                return ToolOutput(success=False, error="Commit message is empty")
+
+            # Security check - prevent path traversal
+            cwd = Path.cwd().resolve()
+            for file in files:
+                if not Path(file).resolve().is_relative_to(cwd):
+                    return ToolOutput(
+                        success=False,
+                        error=f"Access denied: {file} is outside the working directory",
+                    )
+
+            # Verify we're inside a git repository before touching anything
+            repo_check = _run_git("rev-parse", "--is-inside-work-tree")
+            if repo_check.returncode != 0:
+                return ToolOutput(
+                    success=False,
+                    error="Not a git repository (or git is unavailable)",
+                )
+
+            stage = _run_git("add", "--", *files)
+            if stage.returncode != 0:
+                return ToolOutput(
+                    success=False,
+                    error=f"git add failed: {stage.stderr.strip()}",
+                )
+
+            commit = _run_git("commit", "-m", message)
+            if commit.returncode != 0:
+                # "nothing to commit" and hook failures both land here;
+                # surface git's own explanation
+                detail = commit.stderr.strip() or commit.stdout.strip()
+                return ToolOutput(success=False, error=f"git commit failed: {detail}")
+
+            commit_hash = _run_git("rev-parse", "--short", "HEAD").stdout.strip()
+            logger.info(f"Created commit {commit_hash}: {message}")
+
+            return ToolOutput(
+                success=True,
+                data={"commit": commit_hash, "message": message, "files": files},
+                metadata={"files_staged": len(files)},
+            )
+
+        except subprocess.TimeoutExpired:
+            return ToolOutput(success=False, error="git command timed out")
+        except KeyError as e:
+            return ToolOutput(success=False, error=f"Missing required parameter: {e}")
+        except Exception as e:
+            logger.error(f"Git commit failed: {e}")
+            return ToolOutput(success=False, error=str(e))
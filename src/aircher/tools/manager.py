@@ -17,7 +17,12 @@ class ToolManager:
     def __init__(self):
         self.aircher_dir = Path.home() / ".aircher"
         self.tools_dir = self.aircher_dir / "tools" / "bin"
-        self.tools_dir.mkdir(parents=True, exist_ok=True)
+        try:
+            self.tools_dir.mkdir(parents=True, exist_ok=True)
+        except OSError as e:
+            # Read-only home (containers, restricted CI): system tools still
+            # work, we just can't bundle our own
+            logger.warning(f"Tools directory {self.tools_dir} is not writable: {e}")
 
         self.versions_file = self.aircher_dir / "tools" / "versions.json"
         self.versions = self._load_versions()
@@ -33,8 +38,11 @@ class ToolManager:
 
     def _save_versions(self):
         """Save tool versions to file."""
-        self.versions_file.parent.mkdir(parents=True, exist_ok=True)
-        self.versions_file.write_text(json.dumps(self.versions, indent=2))
+        try:
+            self.versions_file.parent.mkdir(parents=True, exist_ok=True)
+            self.versions_file.write_text(json.dumps(self.versions, indent=2))
+        except OSError as e:
+            logger.warning(f"Failed to save tool versions: {e}")
 
     def get_platform(self) -> str:
         """Get platform identifier for downloads."""
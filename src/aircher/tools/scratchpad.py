@@ -0,0 +1,107 @@
+"""Scratchpad tool for the agent's working notes."""
+
+from pathlib import Path
+from typing import Any
+
+from loguru import logger
+
+from .base import BaseTool, ToolInput, ToolOutput
+
+# Fixed location under the project; the tool never writes anywhere else,
+# which is what makes it safe to skip approval in every mode
+SCRATCHPAD_RELATIVE_PATH = Path(".aircher") / "scratch.md"
+
+_ACTIONS = ("read", "write", "append", "clear")
+
+
+def scratchpad_path(project_dir: Path | None = None) -> Path:
+    """Resolve the scratchpad location for a project."""
+    root = project_dir if project_dir is not None else Path.cwd()
+    return root / SCRATCHPAD_RELATIVE_PATH
+
+
+class ScratchpadTool(BaseTool):
+    """Tool for reading and updating a sandboxed notes file.
+
+    The scratchpad lives at a fixed path (.aircher/scratch.md) so the
+    agent can track its plan and progress across a long task without
+    touching real project files or going through write approval.
+    """
+
+    def __init__(self, project_dir: Path | None = None):
+        super().__init__(
+            name="scratchpad",
+            description="Read or update your working-notes file "
+            "(.aircher/scratch.md) to track plan and progress",
+        )
+        self.path = scratchpad_path(project_dir)
+
+    def get_input_schema(self) -> ToolInput:
+        """Get input schema for scratchpad tool."""
+        return ToolInput(
+            name=self.name,
+            description=self.description,
+            parameters={
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": list(_ACTIONS),
+                        "description": "read the notes, write (replace), "
+                        "append a new entry, or clear them",
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "Notes content (required for write/append)",
+                    },
+                },
+                "required": ["action"],
+            },
+        )
+
+    async def execute(self, **kwargs: Any) -> ToolOutput:
+        """Execute a scratchpad operation."""
+        action = str(kwargs.get("action", "")).strip()
+        if action not in _ACTIONS:
+            return ToolOutput(
+                success=False,
+                error=f"Unknown action: {action!r} (expected one of {_ACTIONS})",
+            )
+
+        try:
+            if action == "read":
+                content = self.path.read_text() if self.path.exists() else ""
+                return ToolOutput(
+                    success=True,
+                    data=content,
+                    metadata={"path": str(self.path), "empty": not content.strip()},
+                )
+
+            if action == "clear":
+                if self.path.exists():
+                    self.path.unlink()
+                return ToolOutput(success=True, data="Scratchpad cleared")
+
+            content = kwargs.get("content")
+            if content is None:
+                return ToolOutput(
+                    success=False, error=f"Action '{action}' requires content"
+                )
+
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            if action == "append" and self.path.exists():
+                existing = self.path.read_text()
+                if existing and not existing.endswith("\n"):
+                    existing += "\n"
+                content = existing + content
+            self.path.write_text(content)
+
+            return ToolOutput(
+                success=True,
+                data=f"Scratchpad updated ({len(content)} characters)",
+                metadata={"path": str(self.path), "action": action},
+            )
+
+        except OSError as e:
+            logger.error(f"Scratchpad {action} failed: {e}")
+            return ToolOutput(success=False, error=f"Scratchpad {action} failed: {e}")
@@ -0,0 +1,100 @@
+"""Web search tool for questions the local codebase can't answer."""
+
+import os
+from typing import Any
+
+import httpx
+from loguru import logger
+
+from .base import BaseTool, ToolInput, ToolOutput
+
+# Brave Search API key; web search stays unregistered without it
+SEARCH_API_KEY_ENV = "BRAVE_SEARCH_API_KEY"
+
+_SEARCH_ENDPOINT = "https://api.search.brave.com/res/v1/web/search"
+
+
+def web_search_available() -> bool:
+    """Check whether a search API key is configured."""
+    return bool(os.getenv(SEARCH_API_KEY_ENV))
+
+
+class WebSearchTool(BaseTool):
+    """Tool for searching the web (current docs, APIs, libraries).
+
+    Opt-in via settings.web_search_enabled and gated on a configured
+    search API key; modes without network access never see it.
+    """
+
+    def __init__(self):
+        super().__init__(
+            name="web_search",
+            description="Search the web for current documentation or API information",
+        )
+
+    def get_input_schema(self) -> ToolInput:
+        """Get input schema for web search tool."""
+        return ToolInput(
+            name=self.name,
+            description=self.description,
+            parameters={
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Search query",
+                    },
+                    "max_results": {
+                        "type": "integer",
+                        "description": "Maximum number of results",
+                        "default": 5,
+                    },
+                },
+                "required": ["query"],
+            },
+        )
+
+    async def execute(self, **kwargs: Any) -> ToolOutput:
+        """Execute a web search and return snippet results."""
+        query = str(kwargs.get("query", "")).strip()
+        if not query:
+            return ToolOutput(success=False, error="No query provided")
+        max_results = int(kwargs.get("max_results", 5))
+
+        api_key = os.getenv(SEARCH_API_KEY_ENV)
+        if not api_key:
+            return ToolOutput(
+                success=False,
+                error=f"Web search requires {SEARCH_API_KEY_ENV} to be set",
+            )
+
+        try:
+            async with httpx.AsyncClient(timeout=15.0) as client:
+                response = await client.get(
+                    _SEARCH_ENDPOINT,
+                    params={"q": query, "count": max_results},
+                    headers={
+                        "X-Subscription-Token": api_key,
+                        "Accept": "application/json",
+                    },
+                )
+                response.raise_for_status()
+                payload = response.json()
+        except httpx.HTTPError as e:
+            logger.warning(f"Web search failed: {e}")
+            return ToolOutput(success=False, error=f"Web search failed: {e}")
+
+        results = [
+            {
+                "title": item.get("title", ""),
+                "url": item.get("url", ""),
+                "snippet": item.get("description", ""),
+            }
+            for item in (payload.get("web", {}).get("results") or [])[:max_results]
+        ]
+
+        return ToolOutput(
+            success=True,
+            data=results,
+            metadata={"query": query, "result_count": len(results)},
+        )
@@ -0,0 +1,2110 @@
+"""Interactive terminal UI for Aircher.
+
+A Rich-based chat interface around AircherAgent for local use. The ACP
+server remains the primary integration path for editors; the TUI covers
+direct terminal workflows.
+"""
+
+import asyncio
+import os
+import re
+import shlex
+import shutil
+import signal
+import subprocess
+import tempfile
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from loguru import logger
+from pydantic import BaseModel, Field
+from rich import box
+from rich.console import Console
+from rich.panel import Panel
+from rich.syntax import Syntax
+from rich.text import Text
+
+from ..agent import AircherAgent
+from ..config import effective_config, get_settings
+from ..context import DEFAULT_READ_TOKEN_BUDGET, read_for_context
+from ..models import (
+    REASONING_EFFORTS,
+    SUPPORTED_MODELS,
+    LatencyMonitor,
+    is_context_length_error,
+    provider_is_authenticated,
+    rate_limit_delay,
+    suggest_downgrade,
+)
+from ..modes import AgentMode
+from ..permissions import PermissionsManager
+from ..project import detect_primary_language
+from ..protocol import ACPSession
+from ..protocol import AgentMode as SessionMode
+from ..security import SecretScanner
+from ..tools import BashTool, scratchpad_path
+from ..sessions import SessionStorage
+from ..templates import TemplateStore, expand_template
+from .diff import DiffViewer
+from .display import (
+    MIN_TERMINAL_WIDTH,
+    SPINNER_FRAME_SECONDS,
+    get_streaming_display,
+    layout_width,
+    render_chat_content,
+    terminal_capabilities,
+)
+from .state import UIState, UIStateStore
+
+
+# @image:path/to/screenshot.png attaches an image to the message
+_IMAGE_ATTACHMENT_RE = re.compile(r"@image:(\S+)")
+
+# @file:path/to/module.py injects (budget-elided) file contents
+_FILE_ATTACHMENT_RE = re.compile(r"@file:(\S+)")
+
+# Fenced code blocks, for /copy <n>
+_CODE_BLOCK_RE = re.compile(r"```[^\n]*\n(.*?)```", re.DOTALL)
+
+# Context-window headroom kept free for the model's response
+OUTPUT_TOKEN_RESERVE = 4096
+
+# /read inlines files up to this many lines; longer ones open in a pager
+READ_INLINE_MAX_LINES = 100
+
+# Clipboard tools in preference order; first one present wins
+_CLIPBOARD_COMMANDS = [
+    ["pbcopy"],
+    ["wl-copy"],
+    ["xclip", "-selection", "clipboard"],
+    ["xsel", "--clipboard", "--input"],
+]
+
+
+def copy_to_clipboard(text: str) -> str | None:
+    """Copy text to the system clipboard via an external tool.
+
+    Returns:
+        The name of the tool used, or None if no clipboard tool is
+        available (the caller should fall back to a temp file).
+    """
+    for command in _CLIPBOARD_COMMANDS:
+        if shutil.which(command[0]) is None:
+            continue
+        try:
+            subprocess.run(
+                command, input=text.encode("utf-8"), check=True, timeout=5
+            )
+            return command[0]
+        except (OSError, subprocess.SubprocessError):
+            continue
+    return None
+
+
+def extract_image_attachments(text: str) -> tuple[str, list[str]]:
+    """Split @image:path attachments out of a message.
+
+    Returns:
+        The message with attachment markers removed, and the list of
+        referenced image paths.
+    """
+    paths = _IMAGE_ATTACHMENT_RE.findall(text)
+    clean = _IMAGE_ATTACHMENT_RE.sub("", text)
+    return " ".join(clean.split()), paths
+
+
+def extract_file_attachments(text: str) -> tuple[str, list[str]]:
+    """Split @file:path attachments out of a message."""
+    paths = _FILE_ATTACHMENT_RE.findall(text)
+    clean = _FILE_ATTACHMENT_RE.sub("", text)
+    return " ".join(clean.split()), paths
+
+
+def _content_words(text: str) -> set[str]:
+    """Lowercased words of 3+ chars, for cheap overlap scoring."""
+    return {w for w in re.findall(r"[a-z0-9_]+", text.lower()) if len(w) >= 3}
+
+
+class ChatMessage(BaseModel):
+    """A single message in the TUI conversation."""
+
+    role: str  # "user", "assistant", "system", "tool"
+    content: str
+    timestamp: datetime = Field(default_factory=datetime.now)
+    metadata: dict[str, Any] = Field(default_factory=dict)
+
+
+class TuiManager:
+    """Terminal chat UI around the Aircher agent."""
+
+    def __init__(
+        self,
+        model_name: str = "gpt-4o-mini",
+        project_dir: Path | None = None,
+        enable_memory: bool = True,
+        resume: bool = False,
+    ):
+        self.settings = get_settings()
+        # Degrade rendering on terminals without color/Unicode support
+        # (NO_COLOR, TERM=dumb, non-UTF encodings)
+        capabilities = terminal_capabilities()
+        self.unicode_ok = capabilities["unicode"]
+        self.console = Console(
+            color_system="auto" if capabilities["color"] else None
+        )
+        self._panel_box = box.ROUNDED if self.unicode_ok else box.ASCII
+        self.project_dir = project_dir or Path.cwd()
+
+        # Pull the embedding model (if needed) before agent init so the
+        # first run shows a progress line instead of a frozen terminal
+        if enable_memory:
+            self._predownload_embedding_model()
+
+        self.agent = AircherAgent(model_name=model_name, enable_memory=enable_memory)
+        self.model_name = model_name
+        self.latency_monitor = LatencyMonitor(
+            slow_seconds=self.settings.slow_request_seconds,
+            threshold=self.settings.slow_request_threshold,
+        )
+
+        # Conversation state
+        self.messages: list[ChatMessage] = []
+        self.session_id = f"tui_{datetime.now().strftime('%Y%m%d_%H%M%S')}"
+        self.mode = AgentMode.READ
+        # Hard read-only toggle; stronger than mode - write/execute tools
+        # are never offered to the model while active
+        self.inspect_mode = False
+        # Armed by a first /clear on a non-trivial conversation
+        self._clear_pending = False
+        # Armed by a first /quit while a draft or queue would be lost
+        self._quit_pending = False
+        # /raw-view: render assistant output verbatim for debugging
+        self.raw_view = False
+        # /welcome off hides the startup banner (persisted with UI state)
+        self.welcome_dismissed = False
+        # Shell commands the agent ran this session, newest last (/rerun)
+        self.command_history: list[str] = []
+
+        # Session persistence, keyed by project so /resume picks up the
+        # right conversation per repository
+        self.project = str(self.project_dir.resolve())
+        self.storage = SessionStorage()
+        self._resume_on_start = resume
+        # One-shot related-session context, spent on the first send
+        self._related_injected = False
+
+        # Volatile UI state
+        self.input = ""
+        self.scroll_offset = 0
+        self.running = False
+
+        # Messages staged to send after the current exchange (/queue)
+        self.message_queue: list[str] = []
+        # Per-session system prompt override, persisted in session metadata
+        self.system_prompt_override: str | None = None
+        # Session-level temperature override (config default when None)
+        self.temperature: float | None = None
+        # Session-level thinking budget for reasoning models (/effort)
+        self.reasoning_effort: str | None = None
+        # Request-window cap set when resuming an oversized session
+        self.request_history_limit: int | None = None
+
+        # UI-state autosave (draft input, mode) - separate from message
+        # persistence, recovers unsent work after a crash or accidental quit
+        self.state_store = UIStateStore(
+            project_dir=self.project_dir,
+            interval_seconds=self.settings.ui.autosave_interval,
+        )
+
+        # Fallback highlight language for unhinted code blocks
+        self.code_language = detect_primary_language(self.project_dir) or "text"
+
+        # Approved command patterns (reviewable via /permissions)
+        self.permissions = PermissionsManager()
+
+        # Named prompt templates (/template)
+        self.templates = TemplateStore(project_dir=self.project_dir)
+
+        # Diff rendering (/diff), palette from config for accessibility
+        self.diff_viewer = DiffViewer(
+            self.console, palette=self.settings.ui.diff_palette
+        )
+
+        # Outgoing-message secret scanner (data-leak guardrail)
+        self.secret_scanner: SecretScanner | None = None
+        if self.settings.security.secret_scan_enabled:
+            self.secret_scanner = SecretScanner(
+                extra_patterns=self.settings.security.secret_patterns
+            )
+
+    def _predownload_embedding_model(self) -> None:
+        """Download the embedding model with a visible progress line."""
+        from ..memory.vector_search import ensure_model_available
+
+        def report(description: str, done: int, total: int) -> None:
+            if total:
+                self.console.print(
+                    f"Downloading {description}: "
+                    f"{done // 1048576}/{total // 1048576} MB",
+                    end="\r",
+                )
+
+        ensure_model_available(
+            "sentence-transformers/all-MiniLM-L6-v2", progress_callback=report
+        )
+
+    def _capture_ui_state(self) -> UIState:
+        """Capture current volatile state for persistence."""
+        return UIState(
+            input=self.input,
+            mode=self.mode.value,
+            scroll_offset=self.scroll_offset,
+            session_id=self.session_id,
+            welcome_dismissed=self.welcome_dismissed,
+        )
+
+    def _restore_ui_state(self) -> None:
+        """Restore draft input and mode from a previous run, if saved."""
+        state = self.state_store.load()
+        if state is None:
+            return
+
+        try:
+            self.mode = AgentMode(state.mode)
+        except ValueError:
+            logger.warning(f"Ignoring unknown saved mode: {state.mode}")
+
+        self.welcome_dismissed = state.welcome_dismissed
+
+        if state.input:
+            self.input = state.input
+            self.add_system_message(
+                "Restored unsent draft from previous session (press Enter to send, "
+                "or edit it)"
+            )
+
+    async def _autosave_loop(self) -> None:
+        """Periodically persist volatile UI state."""
+        interval = self.settings.ui.autosave_interval
+        if interval <= 0:
+            return
+
+        while self.running:
+            await asyncio.sleep(interval)
+            self.state_store.maybe_save(self._capture_ui_state())
+
+    def add_system_message(self, content: str) -> None:
+        """Append a system/status message to the conversation."""
+        self.messages.append(ChatMessage(role="system", content=content))
+
+    def add_tool_message(self, content: str) -> None:
+        """Append a tool-status message, coalescing consecutive duplicates.
+
+        Repeated identical statuses ("Reading file...") collapse into one
+        entry with a count so multi-step agent runs stay readable. Only
+        adjacent tool messages coalesce - user/assistant content never
+        does, and the full text stays inspectable on the message.
+        """
+        if self.messages:
+            last = self.messages[-1]
+            if last.role == "tool" and last.content == content:
+                last.metadata["count"] = last.metadata.get("count", 1) + 1
+                last.timestamp = datetime.now()
+                return
+        self.messages.append(ChatMessage(role="tool", content=content))
+
+    def _register_session(self, title: str | None = None) -> None:
+        """Persist the current (fresh) session record."""
+        now = datetime.now()
+        metadata: dict[str, Any] = {"model": self.model_name}
+        if title:
+            metadata["title"] = title
+        self.storage.create_session(
+            ACPSession(
+                id=self.session_id,
+                created_at=now,
+                last_activity=now,
+                mode=SessionMode.READ,
+                project=self.project,
+                metadata=metadata,
+            )
+        )
+
+    def start_new_session(self, title: str | None = None) -> None:
+        """Start a fresh session, leaving the previous one intact.
+
+        The counterpart to /resume: distinct tasks get distinct sessions
+        instead of reusing one. The current model carries over.
+        """
+        # Microseconds keep the id unique even right after startup
+        self.session_id = f"tui_{datetime.now().strftime('%Y%m%d_%H%M%S_%f')}"
+        self.messages.clear()
+        self.system_prompt_override = None
+        self.request_history_limit = None
+        self._register_session(title=title)
+        label = f"{self.session_id}" + (f" ({title})" if title else "")
+        self.add_system_message(f"Started new session {label}")
+
+    def resume_most_recent(self) -> bool:
+        """Switch to the most recent session for this project.
+
+        Returns True if a previous session was loaded.
+        """
+        session = self.storage.most_recent_for_project(self.project)
+        if session is None:
+            self.add_system_message("No previous session for this project")
+            return False
+        if session.id == self.session_id:
+            self.add_system_message("Already on the most recent session")
+            return False
+
+        self.session_id = session.id
+        self.messages = [
+            ChatMessage(
+                role=data["role"],
+                content=data["content"],
+                timestamp=datetime.fromisoformat(data["timestamp"]),
+                metadata=data.get("metadata", {}),
+            )
+            for data in self.storage.get_messages(session.id)
+            if "role" in data
+        ]
+        self.system_prompt_override = session.metadata.get("system_prompt")
+        session.last_activity = datetime.now()
+        self.storage.update_session(session)
+        self.add_system_message(
+            f"Resumed session {session.id} ({len(self.messages) - 1} messages)"
+        )
+        self._maybe_window_resumed_history()
+        return True
+
+    def _maybe_window_resumed_history(self) -> None:
+        """Cap the request window when a resumed session is very large.
+
+        Display history stays full; only what gets sent per request is
+        limited, so resuming a massive session doesn't blow the context
+        budget on the first message. /compact lifts the cap by replacing
+        the history with a summary.
+        """
+        threshold = self.settings.resume_compact_tokens
+        if threshold <= 0:
+            return
+        estimated = sum(len(m.content) for m in self.messages) // 4
+        if estimated <= threshold:
+            return
+
+        # Keep the newest turns that fit inside the threshold
+        budget = threshold
+        keep = 0
+        for message in reversed(self.messages):
+            cost = len(message.content) // 4
+            if cost > budget:
+                break
+            budget -= cost
+            keep += 1
+        self.request_history_limit = max(keep, 2)
+        self.add_system_message(
+            f"Large session (~{estimated} tokens): sending only the last "
+            f"{self.request_history_limit} messages per request. "
+            "Use /compact to summarize the full history."
+        )
+
+    async def run(self) -> None:
+        """Run the TUI main loop."""
+        if self.console.size.width < MIN_TERMINAL_WIDTH:
+            # Too narrow to render anything useful - plain print so even a
+            # tiny window shows the message
+            print(
+                f"Terminal too narrow ({self.console.size.width} cols); "
+                f"aircher needs at least {MIN_TERMINAL_WIDTH}"
+            )
+            return
+
+        self.running = True
+        self._install_suspend_handler()
+        self._restore_ui_state()
+
+        if not (self._resume_on_start and self.resume_most_recent()):
+            self._register_session()
+
+        autosave_task = asyncio.create_task(self._autosave_loop())
+        index_task: asyncio.Task[None] | None = None
+        if self.settings.auto_index_on_start:
+            index_task = asyncio.create_task(self._auto_index())
+
+        self._draw_welcome()
+
+        try:
+            while self.running:
+                line = await asyncio.to_thread(self._read_input)
+                if line is None:
+                    break
+
+                line = line.strip()
+                if not line:
+                    continue
+
+                if line.startswith("/"):
+                    # Commands leave any restored draft intact so /quit can
+                    # warn about it and a cancelled quit costs nothing
+                    await self.handle_command(line)
+                else:
+                    self.input = ""
+                    await self.send_message(line)
+
+                # Drain queued messages in order; the queue stays editable
+                # between sends (a /queue drop mid-drain still applies)
+                while self.message_queue and self.running:
+                    queued = self.message_queue.pop(0)
+                    self.console.print(f"[dim]> {queued}[/dim]")
+                    await self.send_message(queued)
+        except (KeyboardInterrupt, EOFError):
+            pass
+        finally:
+            self.running = False
+            autosave_task.cancel()
+            if index_task is not None:
+                index_task.cancel()
+            # Clean exit: persist final state (keeps any unsent draft)
+            self.state_store.save(self._capture_ui_state())
+
+    async def _auto_index(self) -> None:
+        """Refresh the semantic index in the background at startup.
+
+        Incremental (git-changed files only) when possible so a warm
+        index costs almost nothing; falls back to a full scan outside
+        git. Runs in a worker thread so typing stays responsive, and
+        failures just leave search stale - the UI never blocks on this.
+        """
+        memory = getattr(self.agent, "memory", None)
+        if memory is None:
+            return
+        self.console.print("[dim]indexing project for search...[/dim]")
+
+        def build() -> dict[str, int]:
+            stats = asyncio.run(memory.vector.index_changed(self.project_dir))
+            if stats is None:
+                stats = asyncio.run(memory.vector.index_codebase(self.project_dir))
+            return stats
+
+        try:
+            stats = await asyncio.to_thread(build)
+        except Exception as e:
+            logger.warning(f"Startup indexing failed: {e}")
+            return
+        self.console.print(
+            f"[dim]index ready ({stats['files_indexed']} files refreshed)[/dim]"
+        )
+
+    async def _handle_reindex_command(self, args: str) -> None:
+        """Re-embed a file or directory now (default: the whole project).
+
+        Manual freshness control between background refreshes - after a
+        big edit, /reindex src/foo.py makes /search see it immediately.
+        """
+        memory = getattr(self.agent, "memory", None)
+        if memory is None:
+            self.console.print("[red]Memory systems are disabled (--no-memory)[/red]")
+            return
+        target = Path(args.strip()) if args.strip() else self.project_dir
+        if not target.exists():
+            self.console.print(f"[red]No such path: {target}[/red]")
+            return
+
+        self.console.print(f"[dim]Reindexing {target}...[/dim]")
+
+        def build() -> dict[str, int]:
+            return asyncio.run(memory.vector.reindex_path(target))
+
+        try:
+            stats = await asyncio.to_thread(build)
+        except Exception as e:
+            self.console.print(f"[red]Reindex failed: {e}[/red]")
+            return
+        self.console.print(
+            f"[dim]Reindexed {stats['files_indexed']} files "
+            f"({stats['errors']} errors)[/dim]"
+        )
+
+    def _handle_scratchpad_command(self) -> None:
+        """Show the agent's working notes (.aircher/scratch.md).
+
+        The scratchpad is where the agent tracks its plan and progress on
+        long tasks; this surfaces it without digging for the file.
+        """
+        path = scratchpad_path(self.project_dir)
+        try:
+            content = path.read_text() if path.exists() else ""
+        except OSError as e:
+            self.console.print(f"[red]Failed to read scratchpad: {e}[/red]")
+            return
+        if not content.strip():
+            self.console.print("[dim]Scratchpad is empty[/dim]")
+            return
+        self.console.print(
+            Panel(
+                Syntax(content, "markdown", theme="ansi_dark", word_wrap=True),
+                title="scratchpad",
+                width=layout_width(self.console.size.width, preferred=90),
+            )
+        )
+
+    def _install_suspend_handler(self) -> None:
+        """Re-orient the display when resumed after Ctrl+Z (SIGCONT).
+
+        The TUI runs in cooked mode, so the shell handles the suspend
+        itself; all that's needed on resume is repainting enough state to
+        re-orient, since the terminal may have scrolled while suspended.
+        Disable via ui.handle_suspend if it conflicts with other tooling.
+        """
+        if not self.settings.ui.handle_suspend or not hasattr(signal, "SIGCONT"):
+            return
+
+        def on_resume(signum: int, frame: Any) -> None:
+            self.console.print(
+                f"\n[dim]Resumed ({self.model_name}, {self.mode.value} mode) - "
+                "press Enter to redraw the prompt[/dim]"
+            )
+
+        try:
+            signal.signal(signal.SIGCONT, on_resume)
+        except (ValueError, OSError):
+            # Not on the main thread, or no job control (e.g. some CI)
+            pass
+
+    def _read_input(self) -> str | None:
+        """Read one line of input, prefilled with any restored draft."""
+        queued = f" ({len(self.message_queue)} queued)" if self.message_queue else ""
+        mode_label = "inspect" if self.inspect_mode else self.mode.value
+        prompt = f"[{mode_label}{queued}]> "
+        if self.input:
+            # Show restored draft; Enter sends it as-is
+            self.console.print(f"[dim]draft:[/dim] {self.input}")
+            try:
+                line = input(prompt)
+            except EOFError:
+                return None
+            return line if line.strip() else self.input
+        try:
+            return input(prompt)
+        except EOFError:
+            return None
+
+    async def handle_command(self, line: str) -> None:
+        """Dispatch a slash command."""
+        parts = line.split(maxsplit=1)
+        command = parts[0].lower()
+        args = parts[1] if len(parts) > 1 else ""
+
+        # Any command other than a repeated /clear (or /quit) disarms the
+        # matching pending confirmation
+        if command != "/clear":
+            self._clear_pending = False
+        if command not in ("/quit", "/exit"):
+            self._quit_pending = False
+
+        if command in ("/quit", "/exit"):
+            if (
+                self.settings.ui.confirm_quit_with_draft
+                and (self.input.strip() or self.message_queue)
+                and not self._quit_pending
+            ):
+                self._quit_pending = True
+                what = (
+                    "an unsent draft"
+                    if self.input.strip()
+                    else f"{len(self.message_queue)} queued message(s)"
+                )
+                self.console.print(
+                    f"[yellow]You have {what}; {command} again to quit anyway[/yellow]"
+                )
+                return
+            self.running = False
+        elif command == "/clear":
+            self._handle_clear_command()
+        elif command == "/compact":
+            await self._handle_compact_command()
+        elif command == "/queue":
+            self._handle_queue_command(args)
+        elif command == "/remember":
+            if not args:
+                self.console.print("[red]Usage: /remember <note>[/red]")
+            else:
+                self.agent.project_notes.add(args)
+                self.console.print("[dim]Noted[/dim]")
+        elif command == "/memory":
+            self._handle_memory_command(args)
+        elif command == "/raw":
+            if not args:
+                self.console.print("[red]Usage: /raw <message>[/red]")
+            else:
+                await self.send_message(args, include_context=False)
+        elif command == "/raw-view":
+            self._handle_raw_view_command(args)
+        elif command == "/resume":
+            self.resume_most_recent()
+            self._draw_last_message()
+        elif command == "/new":
+            self.start_new_session(title=args.strip() or None)
+            self._draw_last_message()
+        elif command == "/edit":
+            await self._handle_edit_command(args)
+        elif command == "/compose":
+            await self._handle_compose_command()
+        elif command == "/mode":
+            await self._handle_mode_command(args)
+        elif command == "/inspect":
+            self._handle_inspect_command(args)
+        elif command == "/model":
+            self._handle_model_command(args)
+        elif command == "/stats":
+            self._handle_stats_command()
+        elif command == "/whoami":
+            self._handle_whoami_command()
+        elif command == "/read":
+            self._handle_read_command(args)
+        elif command == "/welcome":
+            self._handle_welcome_command(args)
+        elif command == "/rerun":
+            await self._handle_rerun_command()
+        elif command == "/reindex":
+            await self._handle_reindex_command(args)
+        elif command == "/scratchpad":
+            self._handle_scratchpad_command()
+        elif command == "/diff":
+            if not args:
+                self.console.print("[red]Usage: /diff <file>[/red]")
+            elif not self.diff_viewer.render_file_backup(Path(args.strip())):
+                self.console.print(
+                    f"[red]No backup to diff against for {args.strip()}[/red]"
+                )
+        elif command == "/temperature":
+            self._handle_temperature_command(args)
+        elif command == "/effort":
+            self._handle_effort_command(args)
+        elif command == "/trace":
+            self._handle_trace_command(args)
+        elif command == "/system":
+            self._handle_system_command(args)
+        elif command == "/copy":
+            self._handle_copy_command(args)
+        elif command in ("/tag", "/tags"):
+            self._handle_tag_command(args)
+        elif command == "/template":
+            await self._handle_template_command(args)
+        elif command == "/permissions":
+            await self._handle_permissions_command()
+        elif command == "/tools":
+            self._draw_tools()
+        elif command == "/help":
+            self._draw_help()
+        else:
+            self.console.print(f"[red]Unknown command: {command}[/red]")
+
+    async def _handle_edit_command(self, args: str) -> None:
+        """Edit a previously sent user message and regenerate from there.
+
+        The conversation is truncated at the edited message - it and
+        everything after it are replaced by the new exchange.
+        """
+        user_indices = [
+            i for i, message in enumerate(self.messages) if message.role == "user"
+        ]
+        if not user_indices:
+            self.console.print("[red]No user messages to edit[/red]")
+            return
+
+        if args:
+            try:
+                ordinal = int(args)
+            except ValueError:
+                self.console.print("[red]Usage: /edit [message number][/red]")
+                return
+            if not 1 <= ordinal <= len(user_indices):
+                self.console.print(
+                    f"[red]Message number out of range (1-{len(user_indices)})[/red]"
+                )
+                return
+            index = user_indices[ordinal - 1]
+        else:
+            index = user_indices[-1]
+
+        original = self.messages[index].content
+        new_text = await asyncio.to_thread(
+            self._input_with_prefill, "edit> ", original
+        )
+        if new_text is None or not new_text.strip():
+            self.console.print("[dim]Edit cancelled[/dim]")
+            return
+
+        # Drop the edited message and the now-invalidated tail, then resend
+        del self.messages[index:]
+        await self.send_message(new_text.strip())
+
+    async def _handle_compose_command(self) -> None:
+        """Compose a message in $EDITOR, the way git edits commit messages.
+
+        The one-line prompt is cramped for long multi-line requests; the
+        editor opens prefilled with any pending draft and the saved
+        result is sent. Saving an empty file cancels.
+        """
+        editor = os.environ.get("EDITOR") or os.environ.get("VISUAL") or "vi"
+        with tempfile.NamedTemporaryFile(
+            "w", suffix=".md", prefix="aircher_message_", delete=False
+        ) as handle:
+            if self.input:
+                handle.write(self.input)
+            path = Path(handle.name)
+        try:
+            result = await asyncio.to_thread(
+                subprocess.run, [*shlex.split(editor), str(path)]
+            )
+            if result.returncode != 0:
+                self.console.print(
+                    f"[red]Editor exited with status {result.returncode}; "
+                    "message not sent[/red]"
+                )
+                return
+            text = path.read_text().strip()
+        except OSError as e:
+            self.console.print(f"[red]Failed to launch editor: {e}[/red]")
+            return
+        finally:
+            path.unlink(missing_ok=True)
+
+        if not text:
+            self.console.print("[dim]Empty message, not sent[/dim]")
+            return
+        self.input = ""
+        await self.send_message(text)
+
+    def _input_with_prefill(self, prompt: str, text: str) -> str | None:
+        """Read a line of input prefilled with existing text for editing."""
+        try:
+            import readline
+
+            readline.set_startup_hook(lambda: readline.insert_text(text))
+            try:
+                return input(prompt)
+            except EOFError:
+                return None
+            finally:
+                readline.set_startup_hook()
+        except ImportError:
+            # No readline (e.g. some Windows environments): show the original
+            # and read a replacement
+            self.console.print(f"[dim]original:[/dim] {text}")
+            try:
+                return input(prompt)
+            except EOFError:
+                return None
+
+    def _handle_temperature_command(self, args: str) -> None:
+        """Set the session temperature applied to subsequent messages.
+
+        Usage: /temperature (show) | /temperature <0.0-2.0> | /temperature reset
+        """
+        choice = args.strip().lower()
+        if not choice:
+            current = (
+                self.temperature
+                if self.temperature is not None
+                else self.settings.temperature
+            )
+            source = "session" if self.temperature is not None else "config"
+            self.console.print(f"[dim]Temperature: {current} ({source})[/dim]")
+            return
+        if choice == "reset":
+            self.temperature = None
+            self.console.print(
+                f"[dim]Temperature reset to config ({self.settings.temperature})[/dim]"
+            )
+            return
+        try:
+            value = float(choice)
+        except ValueError:
+            self.console.print("[red]Usage: /temperature <0.0-2.0>[/red]")
+            return
+        if not 0.0 <= value <= 2.0:
+            self.console.print("[red]Temperature must be between 0.0 and 2.0[/red]")
+            return
+        self.temperature = value
+        self.console.print(f"[dim]Temperature set to {value} for this session[/dim]")
+
+    def _handle_effort_command(self, args: str) -> None:
+        """Set the thinking budget for reasoning models.
+
+        Usage: /effort (show) | /effort low|medium|high | /effort reset
+        """
+        choice = args.strip().lower()
+        if not choice:
+            current = self.reasoning_effort or self.settings.reasoning_effort
+            source = "session" if self.reasoning_effort else "config"
+            self.console.print(
+                f"[dim]Effort: {current or 'default'} ({source})[/dim]"
+            )
+            return
+        if choice == "reset":
+            self.reasoning_effort = None
+            self.console.print("[dim]Effort reset to config[/dim]")
+            return
+        if choice not in REASONING_EFFORTS:
+            self.console.print("[red]Usage: /effort low|medium|high|reset[/red]")
+            return
+
+        self.reasoning_effort = choice
+        model_config = SUPPORTED_MODELS.get(self.model_name)
+        if model_config is not None and not model_config.supports_reasoning:
+            self.console.print(
+                f"[yellow]{self.model_name} has no reasoning support - "
+                f"effort applies after switching to a reasoning model[/yellow]"
+            )
+            return
+        self.console.print(f"[dim]Effort set to {choice} for this session[/dim]")
+
+    def _handle_trace_command(self, args: str) -> None:
+        """Toggle the JSONL tool-call trace.
+
+        Usage: /trace (status) | /trace on | /trace off
+        """
+        tracer = self.agent.tracer
+        choice = args.strip().lower()
+        if choice == "on":
+            tracer.enabled = True
+            self.console.print(f"[dim]Tracing tool calls to {tracer.trace_path}[/dim]")
+        elif choice == "off":
+            tracer.enabled = False
+            self.console.print("[dim]Tool tracing disabled[/dim]")
+        elif not choice:
+            state = "on" if tracer.enabled else "off"
+            self.console.print(f"[dim]Tracing {state} ({tracer.trace_path})[/dim]")
+        else:
+            self.console.print("[red]Usage: /trace [on|off][/red]")
+
+    def _handle_system_command(self, args: str) -> None:
+        """Set, show, or clear this session's system-prompt override.
+
+        Usage: /system (show) | /system <text> | /system clear
+
+        The override persists on the session record, so a code-review
+        session keeps its specialized prompt across restarts.
+        """
+        if not args:
+            if self.system_prompt_override:
+                self.console.print(
+                    f"[dim]System prompt: {self.system_prompt_override}[/dim]"
+                )
+            else:
+                self.console.print("[dim]No override. Use /system <text>[/dim]")
+            return
+
+        if args.strip().lower() == "clear":
+            self.system_prompt_override = None
+            self.console.print("[dim]System prompt override cleared[/dim]")
+        else:
+            self.system_prompt_override = args.strip()
+            self.console.print("[dim]System prompt override set[/dim]")
+
+        session = self.storage.get_session(self.session_id)
+        if session is not None:
+            if self.system_prompt_override:
+                session.metadata["system_prompt"] = self.system_prompt_override
+            else:
+                session.metadata.pop("system_prompt", None)
+            self.storage.update_session(session)
+
+    def _handle_copy_command(self, args: str) -> None:
+        """Copy the last assistant message (or one of its code blocks).
+
+        Usage: /copy (whole message) | /copy <n> (nth code block)
+
+        Falls back to writing a temp file when no clipboard tool is
+        available (e.g. over plain SSH).
+        """
+        assistant = [m for m in self.messages if m.role == "assistant"]
+        if not assistant:
+            self.console.print("[red]No assistant message to copy[/red]")
+            return
+        text = assistant[-1].content
+
+        if args:
+            try:
+                ordinal = int(args)
+            except ValueError:
+                self.console.print("[red]Usage: /copy [code block number][/red]")
+                return
+            blocks = _CODE_BLOCK_RE.findall(text)
+            if not 1 <= ordinal <= len(blocks):
+                self.console.print(
+                    f"[red]No code block {ordinal} "
+                    f"({len(blocks)} in last message)[/red]"
+                )
+                return
+            text = blocks[ordinal - 1].rstrip("\n")
+
+        tool = copy_to_clipboard(text)
+        if tool:
+            self.console.print(f"[dim]Copied to clipboard ({tool})[/dim]")
+            return
+
+        # No clipboard access: leave the text somewhere retrievable
+        with tempfile.NamedTemporaryFile(
+            mode="w", suffix=".md", prefix="aircher_copy_", delete=False
+        ) as f:
+            f.write(text)
+        self.console.print(f"[dim]No clipboard tool found; wrote {f.name}[/dim]")
+
+    def _handle_tag_command(self, args: str) -> None:
+        """List or edit the current session's tags.
+
+        Usage: /tags (list) | /tag add <tag> | /tag remove <tag>
+
+        Tags persist on the session record, so sessions can be organized
+        after the fact instead of deciding up front.
+        """
+        session = self.storage.get_session(self.session_id)
+        if session is None:
+            self.console.print("[red]Current session is not persisted[/red]")
+            return
+        tags: list[str] = list(session.metadata.get("tags", []))
+
+        parts = args.split(maxsplit=1)
+        action = parts[0].lower() if parts else ""
+        value = parts[1].strip() if len(parts) > 1 else ""
+
+        if not action:
+            if tags:
+                self.console.print(f"[dim]Tags: {', '.join(tags)}[/dim]")
+            else:
+                self.console.print("[dim]No tags. Use /tag add <tag>[/dim]")
+            return
+
+        if action == "add" and value:
+            if value in tags:
+                self.console.print(f"[dim]Already tagged {value}[/dim]")
+                return
+            tags.append(value)
+        elif action == "remove" and value:
+            if value not in tags:
+                self.console.print(f"[red]No tag {value} on this session[/red]")
+                return
+            tags.remove(value)
+        else:
+            self.console.print("[red]Usage: /tag add <tag> | /tag remove <tag>[/red]")
+            return
+
+        session.metadata["tags"] = tags
+        self.storage.update_session(session)
+        self.console.print(
+            f"[dim]Tags: {', '.join(tags) if tags else '(none)'}[/dim]"
+        )
+
+    def _handle_memory_command(self, args: str) -> None:
+        """List or remove remembered project notes.
+
+        Usage: /memory (list) | /memory remove <n>
+        """
+        notes = self.agent.project_notes.notes
+        if not args:
+            if not notes:
+                self.console.print("[dim]No project notes[/dim]")
+                return
+            for i, note in enumerate(notes, start=1):
+                self.console.print(f"  {i}. {note.note}")
+            return
+
+        parts = args.split()
+        if parts[0] == "remove" and len(parts) == 2:
+            try:
+                ordinal = int(parts[1])
+            except ValueError:
+                self.console.print("[red]Usage: /memory remove <number>[/red]")
+                return
+            if self.agent.project_notes.remove(ordinal - 1):
+                self.console.print("[dim]Removed[/dim]")
+            else:
+                self.console.print(f"[red]Out of range (1-{len(notes)})[/red]")
+        else:
+            self.console.print("[red]Usage: /memory [remove <number>][/red]")
+
+    def _handle_queue_command(self, args: str) -> None:
+        """Inspect or edit the staged-message queue.
+
+        Usage: /queue (list) | /queue <message> | /queue drop | /queue clear
+        """
+        if not args:
+            if not self.message_queue:
+                self.console.print("[dim]Queue is empty[/dim]")
+                return
+            for i, message in enumerate(self.message_queue, start=1):
+                self.console.print(f"  {i}. > {message}")
+            return
+
+        if args == "drop":
+            if self.message_queue:
+                dropped = self.message_queue.pop()
+                self.console.print(f"[dim]Dropped queued message: {dropped}[/dim]")
+            else:
+                self.console.print("[dim]Queue is empty[/dim]")
+        elif args == "clear":
+            count = len(self.message_queue)
+            self.message_queue.clear()
+            self.console.print(f"[dim]Cleared {count} queued messages[/dim]")
+        else:
+            self.message_queue.append(args)
+            self.console.print(
+                f"[dim]Queued ({len(self.message_queue)} waiting)[/dim]"
+            )
+
+    async def _handle_compact_command(self) -> None:
+        """Replace the conversation with a streamed summary.
+
+        The summary is streamed token-by-token so compacting a large history
+        shows progress instead of a frozen "Compacting..." line; only once
+        complete does it replace the conversation.
+        """
+        if not self._require_provider():
+            return
+        turns = [m for m in self.messages if m.role in ("user", "assistant")]
+        if len(turns) < 2:
+            self.console.print("[dim]Nothing to compact[/dim]")
+            return
+
+        transcript = "\n".join(f"{m.role}: {m.content}" for m in turns)
+        prompt = (
+            "Summarize this coding-assistant conversation so it can replace "
+            "the full history. Preserve decisions, open tasks, file paths, "
+            "and constraints. Be concise.\n\n" + transcript
+        )
+
+        self.console.print("[dim]Compacting conversation...[/dim]")
+        try:
+            summary = await self._stream_completion(prompt)
+        except Exception as e:
+            logger.error(f"Compaction failed: {e}")
+            self.add_system_message(f"Compaction failed: {e}")
+            self._draw_last_message()
+            return
+
+        self.messages = [
+            ChatMessage(role="system", content=f"Conversation summary:\n{summary}")
+        ]
+        # The summary fits comfortably; no need to keep windowing requests
+        self.request_history_limit = None
+        self.storage.store_chat_message(
+            self.session_id, "system", self.messages[0].content
+        )
+        self.console.print(
+            f"\n[dim]Compacted {len(turns)} messages into a summary[/dim]"
+        )
+
+    async def _stream_completion(self, prompt: str, temperature: float = 0.3) -> str:
+        """Stream a one-off completion, echoing tokens as they arrive.
+
+        Housekeeping calls use the configured utility model (when set) so
+        summarization doesn't burn main-chat-model tokens.
+        """
+        from ..models import ModelRouter, StreamCostWatcher, model_for_task
+
+        model_name = model_for_task(
+            "summarization",
+            default_model=self.model_name,
+            use_tier_heuristic=False,
+        )
+        router = ModelRouter(default_model=model_name, session_id=self.session_id)
+
+        # Budget is checked as tokens arrive, not just before sending, so
+        # a long response can't silently blow past the limit
+        watcher = StreamCostWatcher(
+            model_name,
+            budget=self.settings.session_cost_budget,
+            spent=self._session_cost(),
+            hard_stop=self.settings.budget_hard_stop,
+        )
+
+        pieces: list[str] = []
+        try:
+            async for chunk in router.astream_chunks(
+                model_name=model_name, prompt=prompt, temperature=temperature
+            ):
+                if chunk.reasoning:
+                    # Thinking text is shown but kept out of the result
+                    self.console.print(f"[dim]{chunk.reasoning}[/dim]", end="")
+                if not chunk.content:
+                    continue
+                pieces.append(chunk.content)
+                self.console.print(chunk.content, end="")
+                warning = watcher.note_output(len(chunk.content))
+                if warning:
+                    self.console.print(f"\n[yellow]{warning}[/yellow]")
+                if watcher.stop:
+                    break
+        except (AttributeError, NotImplementedError):
+            # Model without streaming support: fall back to one response
+            llm = router.get_model(model_name=model_name, temperature=temperature)
+            response = await llm.ainvoke(prompt)
+            pieces = [str(response.content)]
+            self.console.print(pieces[0], end="")
+        return "".join(pieces)
+
+    async def _handle_template_command(self, args: str) -> None:
+        """List, add, or expand a named prompt template.
+
+        Usage: /template list | /template add <name> | /template <name> [file]
+        """
+        parts = args.split()
+        if not parts or parts[0] == "list":
+            templates = self.templates.list_templates()
+            if not templates:
+                self.console.print("[dim]No templates defined[/dim]")
+                return
+            for name, content in sorted(templates.items()):
+                preview = content if len(content) <= 60 else content[:57] + "..."
+                self.console.print(f"  {name}: [dim]{preview}[/dim]")
+            return
+
+        if parts[0] == "add":
+            if len(parts) < 2:
+                self.console.print("[red]Usage: /template add <name>[/red]")
+                return
+            name = parts[1]
+            content = await asyncio.to_thread(input, "template> ")
+            if not content.strip():
+                self.console.print("[dim]Empty template, not saved[/dim]")
+                return
+            self.templates.add(name, content)
+            self.console.print(f"[dim]Saved template: {name}[/dim]")
+            return
+
+        name = parts[0]
+        file_arg = parts[1] if len(parts) > 1 else None
+        template = self.templates.get(name)
+        if template is None:
+            self.console.print(f"[red]Unknown template: {name}[/red]")
+            return
+
+        prefix, suffix = expand_template(template, file_arg=file_arg)
+        if suffix or "{{cursor}}" in template:
+            if suffix:
+                self.console.print(f"[dim]...{suffix}[/dim]")
+            fill = await asyncio.to_thread(
+                self._input_with_prefill, "fill> ", prefix
+            )
+            if fill is None or not fill.strip():
+                self.console.print("[dim]Cancelled[/dim]")
+                return
+            message = fill + suffix
+        else:
+            message = await asyncio.to_thread(
+                self._input_with_prefill, "[template]> ", prefix
+            ) or ""
+            if not message.strip():
+                self.console.print("[dim]Cancelled[/dim]")
+                return
+
+        await self.send_message(message.strip())
+
+    async def _handle_permissions_command(self) -> None:
+        """Review and revoke approved command patterns."""
+        rules = self.permissions.list_rules()
+        if not rules:
+            self.console.print("[dim]No approved command patterns[/dim]")
+            return
+
+        for i, rule in enumerate(rules, start=1):
+            self.console.print(f"  {i}. {rule.pattern} [dim]({rule.scope})[/dim]")
+
+        answer = await asyncio.to_thread(
+            input, "Remove rule number (empty to close): "
+        )
+        answer = answer.strip()
+        if not answer:
+            return
+        try:
+            ordinal = int(answer)
+        except ValueError:
+            self.console.print("[red]Not a number[/red]")
+            return
+        if not 1 <= ordinal <= len(rules):
+            self.console.print(f"[red]Out of range (1-{len(rules)})[/red]")
+            return
+
+        pattern = rules[ordinal - 1].pattern
+        if self.permissions.remove(pattern):
+            self.console.print(f"[dim]Removed: {pattern}[/dim]")
+
+    async def _handle_mode_command(self, args: str) -> None:
+        """Switch agent mode."""
+        if not args:
+            self.console.print(f"Current mode: {self.mode.value}")
+            return
+        try:
+            self.mode = AgentMode(args.strip().lower())
+            self.console.print(f"[dim]Switched to {self.mode.value} mode[/dim]")
+            if self.inspect_mode:
+                self.console.print(
+                    "[yellow]Inspect mode is still active; write/execute "
+                    "stay disabled until /inspect off[/yellow]"
+                )
+        except ValueError:
+            modes = ", ".join(m.value for m in AgentMode)
+            self.console.print(f"[red]Unknown mode. Available: {modes}[/red]")
+
+    async def _handle_rerun_command(self) -> None:
+        """Re-run the last shell command the agent executed this session.
+
+        Commands matching an approved pattern run straight away; anything
+        else still gets a confirmation prompt.
+        """
+        if not self.command_history:
+            self.console.print("[dim]No commands run yet this session[/dim]")
+            return
+        command = self.command_history[-1]
+        if not self.permissions.is_approved(command):
+            answer = await asyncio.to_thread(input, f"Re-run `{command}`? [y/N] ")
+            if answer.strip().lower() not in ("y", "yes"):
+                self.console.print("[dim]Cancelled[/dim]")
+                return
+
+        self.console.print(f"[dim]$ {command}[/dim]")
+        bash = BashTool(self.agent.tool_manager)
+        result = await asyncio.to_thread(bash.execute, command)
+
+        output = result.stdout.rstrip()
+        if result.stderr.strip():
+            output = f"{output}\n{result.stderr.rstrip()}" if output else result.stderr.rstrip()
+        if output:
+            self.console.print(output)
+        self.add_tool_message(f"Reran `{command}` (exit {result.returncode})")
+        self.console.print(f"[dim]{self.messages[-1].content}[/dim]")
+
+    def _handle_welcome_command(self, args: str) -> None:
+        """Show or hide the startup banner; the choice persists per project."""
+        arg = args.strip().lower()
+        if arg == "off":
+            self.welcome_dismissed = True
+            self.state_store.save(self._capture_ui_state())
+            self.console.print("[dim]Welcome banner hidden - /welcome on to restore[/dim]")
+        elif arg == "on":
+            self.welcome_dismissed = False
+            self.state_store.save(self._capture_ui_state())
+            self._draw_welcome()
+        else:
+            self.console.print("[red]Usage: /welcome <on|off>[/red]")
+
+    def _handle_read_command(self, args: str) -> None:
+        """Show a file with highlighting, paging instead of flooding chat.
+
+        Small files render inline; anything longer opens in the system
+        pager (scrollable, full contents) so the conversation stays clean.
+        """
+        if not args:
+            self.console.print("[red]Usage: /read <file>[/red]")
+            return
+        path = Path(args.strip())
+        if not path.is_file():
+            self.console.print(f"[red]Not a file: {path}[/red]")
+            return
+        try:
+            syntax = Syntax.from_path(
+                str(path), line_numbers=True, theme="ansi_dark", word_wrap=False
+            )
+            line_count = path.read_text(errors="replace").count("\n") + 1
+        except OSError as e:
+            self.console.print(f"[red]Failed to read {path}: {e}[/red]")
+            return
+
+        if line_count <= READ_INLINE_MAX_LINES:
+            self.console.print(syntax)
+            return
+        with self.console.pager(styles=True):
+            self.console.print(syntax)
+
+    def _handle_clear_command(self) -> None:
+        """Clear the conversation, confirming when it's non-trivial.
+
+        Messages stay persisted in the session, so this only empties the
+        display; /resume brings the conversation back.
+        """
+        threshold = self.settings.ui.clear_confirm_threshold
+        if (
+            threshold > 0
+            and len(self.messages) > threshold
+            and not self._clear_pending
+        ):
+            self._clear_pending = True
+            self.console.print(
+                f"[yellow]Conversation has {len(self.messages)} messages; "
+                "/clear again to confirm[/yellow]"
+            )
+            return
+        self._clear_pending = False
+        self.messages.clear()
+        self.console.print("[dim]Cleared - /resume to restore this session[/dim]")
+
+    def _session_cost(self) -> float:
+        """Total recorded cost across this session's messages."""
+        return sum(
+            (m.metadata.get("cost_summary") or {}).get("total_cost", 0.0)
+            for m in self.messages
+        )
+
+    async def _check_budget(self) -> bool:
+        """Gate the next send on the session cost budget.
+
+        Only active with budget_hard_stop (warn-only budgets never block).
+        budget_behavior=block refuses until config changes;
+        budget_behavior=prompt asks whether to raise the budget for this
+        session and continue - a deliberate mid-task choice instead of a
+        dead end. Streaming overruns are still caught by StreamCostWatcher;
+        this guards the request before any tokens are spent.
+        """
+        budget = self.settings.session_cost_budget
+        if budget <= 0 or not self.settings.budget_hard_stop:
+            return True
+        spent = self._session_cost()
+        if spent < budget:
+            return True
+
+        if self.settings.budget_behavior != "prompt":
+            self.console.print(
+                f"[red]Session budget ${budget:.2f} reached "
+                f"(spent ${spent:.2f}) - raise session_cost_budget "
+                "to continue[/red]"
+            )
+            return False
+
+        new_budget = budget * 2
+        self.console.print(
+            f"[yellow]Session budget ${budget:.2f} reached "
+            f"(spent ${spent:.2f})[/yellow]"
+        )
+        answer = await asyncio.to_thread(
+            input, f"Raise budget to ${new_budget:.2f} and continue? [y/N] "
+        )
+        if answer.strip().lower() in ("y", "yes"):
+            # Runtime-only raise; the configured value is untouched
+            self.settings.session_cost_budget = new_budget
+            self.console.print(f"[dim]Budget raised to ${new_budget:.2f}[/dim]")
+            return True
+        self.console.print("[dim]Message not sent[/dim]")
+        return False
+
+    def _handle_whoami_command(self) -> None:
+        """Show the effective configuration for this session.
+
+        The shared resolver is overlaid with live session state (current
+        model/mode can differ from config after /model or /mode), so this
+        answers "why is it using this model/key" in one place.
+        """
+        mode_label = "inspect" if self.inspect_mode else self.mode.value
+        pairs = effective_config(
+            model=self.model_name, mode=mode_label, project_dir=self.project_dir
+        )
+        pairs.append(("session", self.session_id))
+        lines = [f"{label}: {value}" for label, value in pairs]
+        self.console.print(
+            Panel(
+                "\n".join(lines),
+                title="whoami",
+                box=self._panel_box,
+                width=layout_width(self.console.size.width, preferred=70),
+            )
+        )
+
+    def _handle_stats_command(self) -> None:
+        """Show session analytics: messages, tokens, cost, per-model use."""
+        total_cost = 0.0
+        total_saved = 0.0
+        total_input = 0
+        total_output = 0
+        by_model: dict[str, dict[str, Any]] = {}
+        for message in self.messages:
+            summary = message.metadata.get("cost_summary") or {}
+            total_cost += summary.get("total_cost", 0.0)
+            total_saved += summary.get("cache_savings", 0.0)
+            total_input += summary.get("total_input_tokens", 0)
+            total_output += summary.get("total_output_tokens", 0)
+            for model, usage in summary.get("by_model", {}).items():
+                bucket = by_model.setdefault(
+                    model,
+                    {"input_tokens": 0, "output_tokens": 0, "cost": 0.0, "calls": 0},
+                )
+                for key in bucket:
+                    bucket[key] += usage.get(key, 0)
+
+        user_count = sum(1 for m in self.messages if m.role == "user")
+        assistant_count = sum(1 for m in self.messages if m.role == "assistant")
+        lines = [
+            f"Messages: {len(self.messages)} "
+            f"({user_count} user, {assistant_count} assistant)",
+            f"Tokens: {total_input:,} in / {total_output:,} out",
+            f"Cost: ${total_cost:.4f}"
+            + (f" (saved ${total_saved:.4f} via prompt cache)" if total_saved else ""),
+        ]
+        if self.messages:
+            elapsed = datetime.now() - self.messages[0].timestamp
+            minutes, seconds = divmod(int(elapsed.total_seconds()), 60)
+            lines.append(f"Duration: {minutes}m{seconds:02d}s")
+        for model in sorted(by_model):
+            usage = by_model[model]
+            lines.append(
+                f"  {model}: {usage['input_tokens']:,} in / "
+                f"{usage['output_tokens']:,} out, ${usage['cost']:.4f} "
+                f"({usage['calls']} calls)"
+            )
+        self.console.print(
+            Panel(
+                "\n".join(lines),
+                title="session stats",
+                box=self._panel_box,
+                width=layout_width(self.console.size.width, preferred=70),
+            )
+        )
+
+    def _handle_model_command(self, args: str) -> None:
+        """Show, list, or switch the session's chat model."""
+        name = args.strip()
+        if not name:
+            self.console.print(f"Current model: {self.model_name}")
+            return
+        if name == "list":
+            for model_name in sorted(SUPPORTED_MODELS):
+                config = SUPPORTED_MODELS[model_name]
+                marker = "*" if model_name == self.model_name else " "
+                self.console.print(
+                    f"{marker} {model_name}  [dim]{config.tier.value}, "
+                    f"ctx {config.context_window // 1000}k, "
+                    f"out {config.max_output_tokens // 1000}k | "
+                    f"{config.capability_badges()}[/dim]"
+                )
+            return
+        if name not in SUPPORTED_MODELS:
+            self.console.print(
+                f"[red]Unknown model: {name}. "
+                f"Known: {', '.join(sorted(SUPPORTED_MODELS))}[/red]"
+            )
+            return
+        self._switch_model(name)
+        self.console.print(f"[dim]Switched to {name}[/dim]")
+
+    def _handle_raw_view_command(self, args: str) -> None:
+        """Toggle verbatim assistant rendering for formatting debugging.
+
+        Markdown and syntax highlighting can mask what the model actually
+        emitted (literal backticks, stray whitespace); raw view shows the
+        text exactly as returned.
+        """
+        arg = args.strip().lower()
+        if arg == "status":
+            self.console.print(f"Raw view: {'on' if self.raw_view else 'off'}")
+            return
+        if arg in ("", "on", "off"):
+            self.raw_view = arg == "on" if arg else not self.raw_view
+        else:
+            self.console.print("[red]Usage: /raw-view [on|off|status][/red]")
+            return
+        if self.raw_view:
+            self.console.print("[dim]Raw view on - assistant output verbatim[/dim]")
+        else:
+            self.console.print("[dim]Raw view off[/dim]")
+        # Re-render so the toggle's effect is visible immediately
+        self._draw_last_message()
+
+    def _handle_inspect_command(self, args: str) -> None:
+        """Toggle hard read-only inspect mode."""
+        arg = args.strip().lower()
+        if arg == "status":
+            state = "active" if self.inspect_mode else "off"
+            self.console.print(f"Inspect mode: {state}")
+            return
+        if arg in ("", "on", "off"):
+            self.inspect_mode = arg == "on" if arg else not self.inspect_mode
+        else:
+            self.console.print("[red]Usage: /inspect [on|off|status][/red]")
+            return
+        if self.inspect_mode:
+            self.console.print(
+                "[yellow]Inspect mode: read-only tools only; write and "
+                "execute are hard-disabled[/yellow]"
+            )
+        else:
+            self.console.print(
+                f"[dim]Inspect mode off; back to {self.mode.value} mode[/dim]"
+            )
+
+    def _confirm_secrets(self, text: str) -> bool:
+        """Warn about suspected secrets in outgoing text; return True to send."""
+        if self.secret_scanner is None:
+            return True
+
+        matches = self.secret_scanner.scan(text)
+        if not matches:
+            return True
+
+        self.console.print(
+            "[bold yellow]Possible secrets detected in your message:[/bold yellow]"
+        )
+        for summary in self.secret_scanner.summarize(matches):
+            self.console.print(f"  - {summary['pattern']}: {summary['preview']}")
+        self.console.print(
+            Panel(
+                self.secret_scanner.mask_text(text, matches),
+                title="Preview (masked)",
+                border_style="yellow",
+                box=self._panel_box,
+            )
+        )
+
+        try:
+            answer = input("Send anyway? [y/N] ")
+        except EOFError:
+            return False
+        return answer.strip().lower() in ("y", "yes")
+
+    def _require_provider(self) -> bool:
+        """Check the current model's provider is authenticated.
+
+        Centralized so everything that talks to the model - sends,
+        /compact, /edit regeneration - shows the same actionable message
+        instead of each path surfacing its own raw provider error.
+        """
+        model_config = SUPPORTED_MODELS.get(self.model_name)
+        if model_config is None or provider_is_authenticated(model_config.provider):
+            return True
+        self.console.print(
+            f"[yellow]This needs an AI provider - no "
+            f"{model_config.provider.value} API key found. Run "
+            "`aircher auth import`, or pick another model with /model[/yellow]"
+        )
+        return False
+
+    def _related_session_block(self, message: str) -> str | None:
+        """Summarize related past sessions for one-time context injection.
+
+        Related means same project and word overlap between the new
+        message and a session's title/first message. Summaries are the
+        stored titles (or first user messages) - nothing is regenerated,
+        so this stays cheap and offline. Returns None when nothing from
+        this project overlaps.
+        """
+        words = _content_words(message)
+        scored: list[tuple[int, datetime, str]] = []
+        for session in self.storage.recent_for_project(self.project):
+            if session.id == self.session_id:
+                continue
+            title = session.metadata.get("title", "")
+            first = next(
+                (
+                    data["content"]
+                    for data in self.storage.get_messages(session.id)
+                    if data.get("role") == "user"
+                ),
+                "",
+            )
+            summary = title or first.split("\n")[0][:120]
+            if not summary:
+                continue
+            score = len(words & _content_words(f"{title} {first}"))
+            if score:
+                scored.append((score, session.last_activity, summary))
+
+        if not scored:
+            return None
+        scored.sort(reverse=True)
+        lines = [
+            f"- {stamp:%Y-%m-%d}: {summary}"
+            for _, stamp, summary in scored[: self.settings.related_session_context]
+        ]
+        self.console.print(
+            f"[dim]Including context from {len(lines)} related "
+            f"session{'s' if len(lines) != 1 else ''}[/dim]"
+        )
+        return (
+            "--- context: previously you worked on (related sessions) ---\n"
+            + "\n".join(lines)
+        )
+
+    async def send_message(self, text: str, include_context: bool = True) -> None:
+        """Send a user message to the agent and display the response.
+
+        Args:
+            text: The message to send.
+            include_context: When False, skip system prompt and memory context
+                (the /raw path).
+        """
+        self._clear_pending = False
+        if not self._require_provider():
+            return
+        if not await self._check_budget():
+            return
+        text, images = extract_image_attachments(text)
+        text, files = extract_file_attachments(text)
+        if not text and not images and not files:
+            return
+
+        # Inject @file contents, elided to a share of the model's window so
+        # a single huge file can't consume the whole context
+        file_blocks: list[str] = []
+        if files:
+            model_config = SUPPORTED_MODELS.get(self.model_name)
+            budget = (
+                model_config.context_window // 8
+                if model_config
+                else DEFAULT_READ_TOKEN_BUDGET
+            )
+            for file_path in files:
+                try:
+                    content, elided = read_for_context(file_path, token_budget=budget)
+                except OSError as e:
+                    self.add_system_message(f"Could not attach {file_path}: {e}")
+                    self._draw_last_message()
+                    return
+                note = " (middle elided)" if elided else ""
+                file_blocks.append(f"--- {file_path}{note} ---\n{content}")
+
+        if not self._confirm_secrets(text):
+            self.add_system_message("Message not sent (suspected secret)")
+            self._draw_last_message()
+            return
+
+        # Opt-in continuity across sessions: summaries of related past
+        # sessions ride along with the first message only
+        inject_related = (
+            not self._related_injected and self.settings.related_session_context > 0
+        )
+        if inject_related:
+            related = self._related_session_block(text)
+            if related:
+                file_blocks.append(related)
+
+        outgoing = "\n\n".join([text, *file_blocks]) if file_blocks else text
+
+        # Prior turns for the request; the agent windows this by
+        # max_history_messages
+        history_messages = self.messages
+        if self.request_history_limit is not None:
+            history_messages = history_messages[-self.request_history_limit :]
+        history = [
+            {"role": m.role, "content": m.content}
+            for m in history_messages
+            if m.role in ("user", "assistant")
+        ]
+
+        # Catch context overflow here instead of as an opaque provider
+        # error - and before anything is persisted, so an unsendable
+        # message never lands in history and the draft stays editable
+        history = self._fit_to_context_window(outgoing, history)
+        if history is None:
+            # Nothing was sent or persisted; put the text back as a draft
+            # so it can be shortened and resent
+            self.input = text
+            self.state_store.maybe_save(self._capture_ui_state())
+            return
+        if inject_related:
+            self._related_injected = True
+
+        self.messages.append(
+            ChatMessage(
+                role="user",
+                content=text,
+                metadata={"images": images} if images else {},
+            )
+        )
+        self.storage.store_chat_message(
+            self.session_id, "user", text, metadata={"images": images} if images else {}
+        )
+
+        # Draft was sent; drop the persisted copy so it isn't restored again
+        self.input = ""
+        self.state_store.maybe_save(self._capture_ui_state())
+
+        style = self.settings.ui.status_style
+        start = time.monotonic()
+        status = self.console.status(
+            get_streaming_display(0.0, style=style, unicode=self.unicode_ok),
+            spinner="dots" if self.unicode_ok else "line",
+        )
+        status.start()
+        updater = asyncio.create_task(self._update_status(status, start))
+        retried = False
+        rate_limit_waited = False
+        try:
+            while True:
+                try:
+                    result = await self.agent.run(
+                        message=outgoing,
+                        mode=self.mode,
+                        session_id=self.session_id,
+                        include_context=include_context,
+                        images=images or None,
+                        history=history or None,
+                        system_prompt_override=self.system_prompt_override,
+                        temperature=self.temperature,
+                        reasoning_effort=self.reasoning_effort,
+                        inspect=self.inspect_mode,
+                    )
+                    break
+                except Exception as e:
+                    # A 429 with a known reset time is a self-recovering
+                    # wait, not a dead end: count it down and retry once
+                    delay = rate_limit_delay(e)
+                    if delay is not None and not rate_limit_waited:
+                        rate_limit_waited = True
+                        status.stop()
+                        await self._rate_limit_countdown(delay)
+                        status.start()
+                        continue
+                    # Context overflow is recoverable: trim the oldest half
+                    # of the history and retry once
+                    if retried or not history or not is_context_length_error(e):
+                        raise
+                    retried = True
+                    history = history[len(history) // 2 :]
+                    self.add_system_message(
+                        "Request exceeded the context window - retrying with "
+                        "older history trimmed (/compact to shrink it for good)"
+                    )
+                    self._draw_last_message()
+        except Exception as e:
+            logger.error(f"Agent request failed: {e}")
+            self.latency_monitor.record(
+                self.model_name, time.monotonic() - start, failed=True
+            )
+            self.add_system_message(f"Error: {e}")
+            self._draw_last_message()
+            self._maybe_suggest_downgrade()
+            return
+        finally:
+            updater.cancel()
+            status.stop()
+        self.latency_monitor.record(self.model_name, time.monotonic() - start)
+
+        # Surface tool activity, coalescing repeats ("Ran read_file (×3)")
+        first_new = len(self.messages)
+        for tool_result in result.get("metadata", {}).get("tool_results", []):
+            name = tool_result.get("tool", "tool")
+            command = tool_result.get("parameters", {}).get("command")
+            if name == "bash" and command:
+                self.command_history.append(command)
+            parallel = " (parallel)" if tool_result.get("concurrent") else ""
+            if "error" in tool_result:
+                self.add_tool_message(f"{name} failed: {tool_result['error']}")
+            else:
+                self.add_tool_message(f"Ran {name}{parallel}")
+        for message in self.messages[first_new:]:
+            count = message.metadata.get("count", 1)
+            suffix = f" (×{count})" if count > 1 else ""
+            self.console.print(f"[dim]{message.content}{suffix}[/dim]")
+
+        response = result.get("response", "")
+        # Whitespace-only completions (content filter, refusal, provider
+        # bug) become a system note instead of a blank assistant message
+        if not str(response).strip():
+            finish_reason = result.get("metadata", {}).get("finish_reason")
+            if finish_reason and finish_reason not in ("stop", "end_turn"):
+                note = (
+                    "Model stopped without content "
+                    f"(finish reason: {finish_reason}) - try rephrasing"
+                )
+            else:
+                note = (
+                    "Model returned an empty response - possible content "
+                    "filter; try sending again or rephrasing"
+                )
+            self.add_system_message(note)
+            self._draw_last_message()
+            self._maybe_suggest_downgrade()
+            return
+
+        metadata: dict[str, Any] = {"cost_summary": result.get("cost_summary", {})}
+        if result.get("reasoning"):
+            metadata["reasoning"] = result["reasoning"]
+        self.messages.append(
+            ChatMessage(role="assistant", content=response, metadata=metadata)
+        )
+        self.storage.store_chat_message(
+            self.session_id, "assistant", response, metadata=metadata
+        )
+        self._draw_last_message()
+        self._maybe_suggest_downgrade()
+        if self.settings.auto_title_sessions:
+            await self._maybe_autotitle()
+
+    async def _maybe_autotitle(self) -> None:
+        """Title the session from its first exchange.
+
+        Untitled sessions all read alike in the browser; a short generated
+        title ("Fix auth token refresh bug") makes them scannable. Runs
+        once, only when no explicit title was given, and uses the
+        summarization utility model so it doesn't burn main-chat tokens.
+        """
+        exchange = [m for m in self.messages if m.role in ("user", "assistant")]
+        if len(exchange) != 2:
+            return
+        session = self.storage.get_session(self.session_id)
+        if session is None or session.metadata.get("title"):
+            return
+
+        from ..models import ModelRouter, model_for_task
+
+        model_name = model_for_task(
+            "summarization", default_model=self.model_name, use_tier_heuristic=False
+        )
+        prompt = (
+            "Summarize this coding session as a title of at most 8 words. "
+            "No quotes, no trailing punctuation.\n\n"
+            f"User: {exchange[0].content[:500]}\n"
+            f"Assistant: {exchange[1].content[:500]}"
+        )
+        try:
+            router = ModelRouter(default_model=model_name, session_id=self.session_id)
+            llm = router.get_model(model_name=model_name, temperature=0.0)
+            response = await llm.ainvoke(prompt)
+        except Exception as e:
+            logger.warning(f"Session title generation failed: {e}")
+            return
+        title = str(response.content).strip().strip('"').split("\n")[0][:80]
+        if not title:
+            return
+        session.metadata["title"] = title
+        self.storage.update_session(session)
+        self.console.print(f"[dim]Session titled: {title}[/dim]")
+
+    def _maybe_suggest_downgrade(self) -> None:
+        """Suggest a faster model after a run of slow or failed requests.
+
+        Only suggests by default; switching happens automatically when
+        auto_downgrade is opted into.
+        """
+        if not self.latency_monitor.downgrade_due(self.model_name):
+            return
+        streak = self.latency_monitor.consecutive_slow(self.model_name)
+        target = suggest_downgrade(self.model_name, self.settings.model_downgrades)
+        self.latency_monitor.reset(self.model_name)
+        if target is None:
+            return
+        if self.settings.auto_downgrade:
+            slow = self.model_name
+            self._switch_model(target)
+            self.add_system_message(
+                f"{slow} was slow or failing for {streak} requests in a row; "
+                f"switched to {target} (auto_downgrade is on)"
+            )
+        else:
+            self.add_system_message(
+                f"{self.model_name} was slow or failing for {streak} requests "
+                f"in a row - switch to a faster model? /model {target}"
+            )
+        self._draw_last_message()
+
+    def _switch_model(self, name: str) -> None:
+        """Switch the session's chat model (takes effect next request)."""
+        self.model_name = name
+        self.agent.model_name = name
+
+    def _fit_to_context_window(
+        self, outgoing: str, history: list[dict[str, str]]
+    ) -> list[dict[str, str]] | None:
+        """Verify the request fits the model's context window before sending.
+
+        Older history is trimmed first; if the message alone can't fit
+        (leaving headroom for the response), an actionable error replaces
+        the provider's opaque overflow failure.
+
+        Returns:
+            The (possibly trimmed) history, or None if the request cannot
+            be sent at all.
+        """
+        config = SUPPORTED_MODELS.get(self.model_name)
+        if config is None:
+            return history
+
+        budget = config.context_window - OUTPUT_TOKEN_RESERVE
+        message_tokens = len(outgoing) // 4
+        if message_tokens > budget:
+            self.console.print(
+                f"[red]Message is ~{message_tokens} tokens; {self.model_name} "
+                f"fits {budget} (after response headroom). Shorten it or "
+                f"attach less with @file:[/red]"
+            )
+            return None
+
+        trimmed = 0
+        while history and message_tokens + sum(
+            len(turn["content"]) // 4 for turn in history
+        ) > budget:
+            history.pop(0)
+            trimmed += 1
+        if trimmed:
+            self.console.print(
+                f"[dim]Trimmed {trimmed} older messages to fit "
+                f"{self.model_name}'s context window (/compact to summarize)[/dim]"
+            )
+        return history
+
+    async def _rate_limit_countdown(self, delay: float) -> None:
+        """Count down a provider rate-limit window before retrying.
+
+        The duration comes from the 429's Retry-After header (see
+        rate_limit_delay); the line rewrites in place so the wait reads
+        as one live status, not a scrolling log.
+        """
+        remaining = max(int(delay) + (1 if delay % 1 else 0), 1)
+        while remaining > 0:
+            self.console.print(
+                f"[yellow]rate limited - retrying in {remaining}s[/yellow]",
+                end="\r",
+            )
+            await asyncio.sleep(1)
+            remaining -= 1
+        # Clear the countdown line before the retry's status takes over
+        self.console.print(" " * 40, end="\r")
+
+    async def _update_status(self, status: Any, start: float) -> None:
+        """Refresh the in-flight status line on a steady wall-clock tick.
+
+        The tick matches the spinner frame period, so the spinner animates
+        smoothly even during long tool steps when no chunks arrive to
+        trigger a redraw. Sleeping to the next frame boundary (rather than
+        a fixed interval) keeps the cadence from drifting.
+        """
+        style = self.settings.ui.status_style
+        while True:
+            elapsed = time.monotonic() - start
+            status.update(
+                get_streaming_display(elapsed, style=style, unicode=self.unicode_ok)
+            )
+            await asyncio.sleep(
+                SPINNER_FRAME_SECONDS
+                - (time.monotonic() - start) % SPINNER_FRAME_SECONDS
+            )
+
+    def _terminal_usable(self) -> bool:
+        """Check the live terminal size before drawing panels.
+
+        The startup check only runs once; a mid-session shrink would
+        otherwise garble panel rendering. Prints a plain placeholder
+        (a panel might not fit) when too small.
+        """
+        width = self.console.size.width
+        if width >= MIN_TERMINAL_WIDTH:
+            return True
+        print(
+            f"Terminal too narrow ({width} cols); "
+            f"resize to at least {MIN_TERMINAL_WIDTH}"
+        )
+        return False
+
+    def _draw_welcome(self) -> None:
+        """Draw the welcome banner (unless suppressed or dismissed)."""
+        if not self.settings.ui.show_welcome or self.welcome_dismissed:
+            return
+        if not self._terminal_usable():
+            return
+        self.console.print(
+            Panel(
+                Text.from_markup(
+                    f"[bold]Aircher[/bold] - {self.model_name}\n"
+                    f"Mode: {self.mode.value} | /help for commands"
+                ),
+                box=self._panel_box,
+                width=layout_width(self.console.size.width, preferred=55),
+            )
+        )
+
+    def _draw_tools(self) -> None:
+        """List currently enabled (and disabled) agent tools."""
+        for tool in self.agent.tools:
+            self.console.print(f"  {tool.name} - {tool.description}")
+        for name in sorted(self.agent.disabled_tools):
+            self.console.print(f"  [dim]{name} - disabled by configuration[/dim]")
+
+    def _draw_help(self) -> None:
+        """Draw available commands."""
+        self.console.print(
+            "/mode <read|edit|turbo> - switch agent mode\n"
+            "/inspect [on|off] - hard read-only mode (no write/execute tools)\n"
+            "/model [name|list] - show, list (with capabilities), or switch models\n"
+            "/stats - session message, token, cost, and per-model totals\n"
+            "/whoami - effective model, provider, mode, and config\n"
+            "/read <file> - view a file with highlighting (pages large files)\n"
+            "/welcome <on|off> - show or hide the startup banner\n"
+            "/rerun - re-run the agent's last shell command\n"
+            "/reindex [path] - re-embed a path for search (default: project)\n"
+            "/scratchpad - show the agent's working notes\n"
+            "/raw <message> - send without system prompt or project context\n"
+            "/raw-view [on|off] - show assistant output verbatim (no markdown)\n"
+            "/resume - reopen the most recent session for this project\n"
+            "/new [title] - start a fresh session, keeping the old one\n"
+            "/tag add|remove <tag> - edit this session's tags (/tags to list)\n"
+            "/copy [n] - copy the last assistant message (or its nth code block)\n"
+            "/system [text|clear] - per-session system prompt override\n"
+            "/trace [on|off] - JSONL audit trail of tool calls\n"
+            "/temperature [value|reset] - session sampling temperature\n"
+            "/effort [low|medium|high|reset] - thinking budget (reasoning models)\n"
+            "/edit [n] - edit the nth (default: last) user message and regenerate\n"
+            "/compose - write a long message in $EDITOR (prefills the draft)\n"
+            "/diff <file> - show changes against the file's .bak backup\n"
+            "/template <name> [file] - expand a saved prompt template "
+            "(/template list, /template add <name>)\n"
+            "/permissions - review and revoke approved command patterns\n"
+            "/tools - list enabled agent tools\n"
+            "/remember <note> - save a durable project note\n"
+            "/memory [remove <n>] - list or remove project notes\n"
+            "/queue [message|drop|clear] - stage messages to send next\n"
+            "/compact - replace the conversation with a streamed summary\n"
+            "/clear - clear conversation\n"
+            "/quit - exit"
+        )
+
+    def _draw_last_message(self) -> None:
+        """Render the most recent message."""
+        if not self.messages:
+            return
+        if not self._terminal_usable():
+            return
+        message = self.messages[-1]
+        style = {"assistant": "green", "system": "yellow", "tool": "dim"}.get(
+            message.role, "white"
+        )
+        # Reasoning-model thinking renders dimmed above the answer so it
+        # never reads as part of the final response
+        width = layout_width(
+            self.console.size.width, preferred=self.settings.ui.max_message_width
+        )
+        reasoning = message.metadata.get("reasoning")
+        if reasoning:
+            self.console.print(
+                Panel(
+                    Text(reasoning, style="dim"),
+                    title="[dim]thinking[/dim]",
+                    border_style="dim",
+                    box=self._panel_box,
+                    width=width,
+                )
+            )
+        # Assistant messages get syntax-highlighted code blocks, unless
+        # /raw-view wants the text exactly as the model returned it
+        if message.role == "assistant":
+            if self.raw_view:
+                body: Any = Text(message.content)
+            else:
+                body = render_chat_content(
+                    message.content, default_language=self.code_language
+                )
+        else:
+            body = message.content
+            count = message.metadata.get("count", 1)
+            if message.role == "tool" and count > 1:
+                body = f"{body} (×{count})"
+        self.console.print(
+            Panel(
+                body,
+                border_style=style,
+                box=self._panel_box,
+                width=width,
+            )
+        )
@@ -0,0 +1,79 @@
+"""Diff rendering for the terminal UI."""
+
+import difflib
+from pathlib import Path
+
+from rich.console import Console
+from rich.text import Text
+
+# Styles per diff line kind. The colorblind palette avoids the red/green
+# axis (blue/orange) for deuteranopia/protanopia; the +/- prefixes from
+# unified diff format remain the non-color channel in both palettes.
+PALETTES: dict[str, dict[str, str]] = {
+    "default": {"add": "green", "delete": "red", "hunk": "cyan", "meta": "bold"},
+    "colorblind": {
+        "add": "bright_blue",
+        "delete": "dark_orange",
+        "hunk": "cyan",
+        "meta": "bold",
+    },
+}
+
+
+def generate_diff(old: str, new: str, path: str = "") -> list[str]:
+    """Generate unified diff lines between two versions of a file."""
+    return list(
+        difflib.unified_diff(
+            old.splitlines(),
+            new.splitlines(),
+            fromfile=f"a/{path}" if path else "a",
+            tofile=f"b/{path}" if path else "b",
+            lineterm="",
+        )
+    )
+
+
+class DiffViewer:
+    """Render unified diffs with a configurable palette."""
+
+    def __init__(self, console: Console, palette: str = "default"):
+        self.console = console
+        if palette not in PALETTES:
+            palette = "default"
+        self.palette = PALETTES[palette]
+
+    def render(self, old: str, new: str, path: str = "") -> None:
+        """Render the diff between two file versions."""
+        lines = generate_diff(old, new, path)
+        if not lines:
+            self.console.print("[dim]No changes[/dim]")
+            return
+        for line in lines:
+            self.console.print(Text(line, style=self._style_for(line)))
+
+    def _style_for(self, line: str) -> str:
+        """Pick the palette style for a diff line."""
+        if line.startswith(("+++", "---")):
+            return self.palette["meta"]
+        if line.startswith("@@"):
+            return self.palette["hunk"]
+        if line.startswith("+"):
+            return self.palette["add"]
+        if line.startswith("-"):
+            return self.palette["delete"]
+        return ""
+
+    def render_file_backup(self, path: Path) -> bool:
+        """Render the diff between a file and its .bak backup.
+
+        Returns False when either side is missing.
+        """
+        backup = path.with_suffix(path.suffix + ".bak")
+        if not path.exists() or not backup.exists():
+            return False
+        self.render(
+            backup.read_text(encoding="utf-8", errors="replace"),
+            path.read_text(encoding="utf-8", errors="replace"),
+            path=str(path),
+        )
+        return True
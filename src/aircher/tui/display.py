@@ -0,0 +1,176 @@
+"""Streaming status display for the TUI."""
+
+import os
+import re
+import sys
+
+SPINNER_FRAMES = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]
+
+# Degraded spinner for terminals without Unicode support
+ASCII_SPINNER_FRAMES = ["|", "/", "-", "\\"]
+
+
+def terminal_capabilities(
+    env: dict[str, str] | None = None, encoding: str | None = None
+) -> dict[str, bool]:
+    """Detect what the terminal can render.
+
+    Respects the NO_COLOR convention (https://no-color.org) and degrades
+    on dumb terminals and non-UTF encodings, keeping output legible over
+    constrained SSH sessions and in CI-captured logs.
+
+    Returns:
+        {"color": ..., "unicode": ...} render capability flags.
+    """
+    env = dict(os.environ) if env is None else env
+    encoding = encoding if encoding is not None else (sys.stdout.encoding or "")
+    dumb = env.get("TERM", "") in ("", "dumb", "unknown")
+    return {
+        "color": "NO_COLOR" not in env and not dumb,
+        "unicode": "utf" in encoding.lower() and not dumb,
+    }
+
+# Seconds per spinner frame; the status refresh tick matches this so the
+# animation stays smooth regardless of when chunks arrive
+SPINNER_FRAME_SECONDS = 0.08
+
+# Fenced code blocks with an optional language hint (```python)
+_FENCE_RE = re.compile(r"```([^\n]*)\n(.*?)```", re.DOTALL)
+
+
+def split_fenced_blocks(content: str) -> list[tuple[str, str | None]]:
+    """Split message content into prose and fenced-code segments.
+
+    Returns:
+        (text, language) pairs in order. Language is None for prose and
+        "" for a fence without a hint (the caller picks a fallback).
+    """
+    segments: list[tuple[str, str | None]] = []
+    pos = 0
+    for match in _FENCE_RE.finditer(content):
+        prose = content[pos : match.start()].strip("\n")
+        if prose:
+            segments.append((prose, None))
+        segments.append((match.group(2).rstrip("\n"), match.group(1).strip()))
+        pos = match.end()
+    tail = content[pos:].strip("\n")
+    if tail:
+        segments.append((tail, None))
+    if not segments:
+        segments.append((content, None))
+    return segments
+
+
+def render_chat_content(content: str, default_language: str = "text"):
+    """Build a renderable with syntax-highlighted code blocks.
+
+    Fence language hints are honored; unhinted blocks fall back to the
+    project's primary language so generated code still highlights.
+    """
+    from rich.console import Group
+    from rich.syntax import Syntax
+    from rich.text import Text
+
+    renderables = []
+    for text, language in split_fenced_blocks(content):
+        if language is None:
+            renderables.append(Text(text))
+        else:
+            renderables.append(
+                Syntax(
+                    text,
+                    language or default_language,
+                    theme="ansi_dark",
+                    word_wrap=True,
+                )
+            )
+    return Group(*renderables)
+
+# Below this the UI can't render anything useful
+MIN_TERMINAL_WIDTH = 20
+
+# Content never stretches wider than this, even on ultra-wide terminals
+MAX_CONTENT_WIDTH = 100
+
+
+def layout_width(terminal_width: int, preferred: int = MAX_CONTENT_WIDTH) -> int:
+    """Responsive content width for the current terminal.
+
+    Caps at the preferred width on wide terminals (content hugging the
+    left edge across 300 columns is unreadable) and shrinks with a small
+    margin on narrow ones, never below the renderable minimum. Callers
+    can raise the cap past MAX_CONTENT_WIDTH by passing a wider
+    preference (ui.max_message_width does this for chat panels).
+
+    Args:
+        terminal_width: Current terminal width in columns.
+        preferred: Desired content width for this element.
+
+    Returns:
+        The width to render at.
+    """
+    if terminal_width <= 0:
+        return preferred
+    available = max(terminal_width - 2, 1)  # breathing room for borders
+    # Never exceed the actual terminal: a floor wider than the window
+    # just garbles wrapping when the user shrinks it mid-session
+    return max(min(MIN_TERMINAL_WIDTH - 2, available), min(preferred, available))
+
+# Archery-themed status messages, rotated while a request is in flight
+HUSTLING_MESSAGES = [
+    "Nocking arrow...",
+    "Drawing the bow...",
+    "Taking aim...",
+    "Steadying breath...",
+]
+
+STREAMING_MESSAGES = [
+    "Arrow in flight...",
+    "Tracking the shot...",
+    "Closing on the target...",
+]
+
+# Plain equivalents for users who prefer unadorned status text
+PLAIN_MESSAGES = {
+    "thinking": "Thinking",
+    "streaming": "Receiving",
+}
+
+_FUN_MESSAGES = {
+    "thinking": HUSTLING_MESSAGES,
+    "streaming": STREAMING_MESSAGES,
+}
+
+
+def get_streaming_display(
+    elapsed_seconds: float,
+    phase: str = "thinking",
+    style: str = "fun",
+    unicode: bool = True,
+) -> str:
+    """Build the status line shown while waiting on the model.
+
+    Args:
+        elapsed_seconds: Seconds since the request started.
+        phase: "thinking" (waiting for first content) or "streaming".
+        style: config.ui.status_style - "fun" (themed messages), "plain"
+            (simple status words), or "minimal" (spinner and elapsed time only).
+        unicode: False swaps in the ASCII spinner for degraded terminals.
+
+    Returns:
+        The formatted status line.
+    """
+    frames = SPINNER_FRAMES if unicode else ASCII_SPINNER_FRAMES
+    frame = frames[int(elapsed_seconds / SPINNER_FRAME_SECONDS) % len(frames)]
+
+    if style == "minimal":
+        return f"{frame} {elapsed_seconds:.0f}s"
+
+    if style == "plain":
+        label = PLAIN_MESSAGES.get(phase, "Working")
+        return f"{frame} {label}... ({elapsed_seconds:.0f}s)"
+
+    # Default: themed messages, rotating every 3 seconds
+    messages = _FUN_MESSAGES.get(phase, HUSTLING_MESSAGES)
+    message = messages[int(elapsed_seconds // 3) % len(messages)]
+    return f"{frame} {message} ({elapsed_seconds:.0f}s)"
@@ -0,0 +1,79 @@
+"""Volatile TUI UI-state persistence (draft input, mode, scroll position).
+
+Message history is persisted separately by session storage; this module only
+covers in-progress state that would otherwise be lost on a crash or
+accidental quit.
+"""
+
+import json
+import time
+from datetime import datetime
+from pathlib import Path
+
+from loguru import logger
+from pydantic import BaseModel, Field
+
+
+class UIState(BaseModel):
+    """Snapshot of volatile TUI state."""
+
+    input: str = ""
+    mode: str = "read"
+    scroll_offset: int = 0
+    session_id: str | None = None
+    # /welcome off persists here so the banner stays hidden across runs
+    welcome_dismissed: bool = False
+    updated_at: datetime = Field(default_factory=datetime.now)
+
+
+class UIStateStore:
+    """Periodic persistence of UI state to the project directory."""
+
+    def __init__(self, project_dir: Path, interval_seconds: int = 30):
+        """Initialize the store.
+
+        Args:
+            project_dir: Project root; state is written to .aircher/ui_state.json.
+            interval_seconds: Minimum seconds between throttled saves (0 disables
+                periodic autosave; explicit save() still works).
+        """
+        self.state_path = project_dir / ".aircher" / "ui_state.json"
+        self.interval_seconds = interval_seconds
+        self._last_save: float = 0.0
+
+    def save(self, state: UIState) -> bool:
+        """Persist UI state immediately."""
+        try:
+            self.state_path.parent.mkdir(parents=True, exist_ok=True)
+            self.state_path.write_text(state.model_dump_json())
+            self._last_save = time.monotonic()
+            return True
+        except OSError as e:
+            logger.warning(f"Failed to save UI state: {e}")
+            return False
+
+    def maybe_save(self, state: UIState) -> bool:
+        """Persist UI state if the autosave interval has elapsed."""
+        if self.interval_seconds <= 0:
+            return False
+        if time.monotonic() - self._last_save < self.interval_seconds:
+            return False
+        return self.save(state)
+
+    def load(self) -> UIState | None:
+        """Load previously saved UI state, if any."""
+        if not self.state_path.exists():
+            return None
+        try:
+            data = json.loads(self.state_path.read_text())
+            return UIState(**data)
+        except (OSError, ValueError) as e:
+            logger.warning(f"Failed to load UI state: {e}")
+            return None
+
+    def clear(self) -> None:
+        """Remove persisted UI state (e.g. after the draft was sent)."""
+        try:
+            self.state_path.unlink(missing_ok=True)
+        except OSError as e:
+            logger.warning(f"Failed to clear UI state: {e}")
@@ -0,0 +1,37 @@
+"""Shared fixtures for unit tests."""
+
+import subprocess
+
+import pytest
+
+
+def _git(path, *args):
+    """Run git in a test repo."""
+    subprocess.run(
+        ["git", "-C", str(path), *args],
+        check=True,
+        capture_output=True,
+        env={
+            "GIT_AUTHOR_NAME": "test",
+            "GIT_AUTHOR_EMAIL": "test@example.com",
+            "GIT_COMMITTER_NAME": "test",
+            "GIT_COMMITTER_EMAIL": "test@example.com",
+            "PATH": "/usr/bin:/bin:/usr/local/bin",
+        },
+    )
+
+
+@pytest.fixture
+def git():
+    """The git helper, for tests that drive the repo directly."""
+    return _git
+
+
+@pytest.fixture
+def git_repo(tmp_path):
+    """A fresh git repo with one commit."""
+    _git(tmp_path, "init", "-b", "main")
+    (tmp_path / "README.md").write_text("hello\n")
+    _git(tmp_path, "add", ".")
+    _git(tmp_path, "commit", "-m", "initial")
+    return tmp_path
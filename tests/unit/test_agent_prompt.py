@@ -0,0 +1,53 @@
+"""Tests for system prompt budgeting."""
+
+from aircher.agent import budget_prompt_sections, budget_system_prompt
+
+
+class TestBudgetSystemPrompt:
+    """Test priority-ordered section truncation."""
+
+    def test_under_budget_keeps_everything(self):
+        """Test all sections survive when they fit."""
+        sections = ["instructions", "git state", "notes"]
+
+        assert budget_system_prompt(sections, 1000) == "\n\n".join(sections)
+
+    def test_low_priority_sections_dropped_first(self):
+        """Test later sections are dropped when over budget."""
+        sections = ["instructions", "x" * 4000, "y" * 4000]
+
+        result = budget_system_prompt(sections, 500)
+
+        assert "instructions" in result
+        assert "x" not in result
+        assert "y" not in result
+
+    def test_instructions_always_survive(self):
+        """Test the first section is kept even when alone it exceeds the budget."""
+        sections = ["i" * 8000, "notes"]
+
+        result = budget_system_prompt(sections, 100)
+
+        assert result.startswith("i" * 100)
+        assert "notes" not in result
+
+    def test_zero_budget_disables(self):
+        """Test a zero budget keeps everything."""
+        sections = ["instructions", "x" * 40000]
+
+        assert budget_system_prompt(sections, 0) == "\n\n".join(sections)
+
+class TestBudgetPromptSections:
+    """Test the section-list form used for role splitting."""
+
+    def test_returns_surviving_sections_in_order(self):
+        """Test sections come back as a list for per-role message building."""
+        sections = ["instructions", "git state", "z" * 8000]
+
+        assert budget_prompt_sections(sections, 500) == ["instructions", "git state"]
+
+    def test_zero_budget_keeps_all_sections(self):
+        """Test a zero budget disables dropping."""
+        sections = ["instructions", "x" * 40000]
+
+        assert budget_prompt_sections(sections, 0) == sections
@@ -0,0 +1,52 @@
+"""Tests for reasoning extraction from chat responses."""
+
+from aircher.agent import _extract_reasoning
+
+
+class _FakeResponse:
+    """Minimal stand-in for a LangChain chat message."""
+
+    def __init__(self, content="", additional_kwargs=None):
+        self.content = content
+        self.additional_kwargs = additional_kwargs or {}
+
+
+class TestExtractReasoning:
+    """Test pulling reasoning/thinking text out of provider responses."""
+
+    def test_openai_reasoning_content(self):
+        """Test reasoning_content in additional_kwargs is returned."""
+        response = _FakeResponse(
+            content="The answer",
+            additional_kwargs={"reasoning_content": "Let me think..."},
+        )
+
+        assert _extract_reasoning(response) == "Let me think..."
+
+    def test_openai_reasoning_alias(self):
+        """Test the bare reasoning key is also honored."""
+        response = _FakeResponse(additional_kwargs={"reasoning": "step 1"})
+
+        assert _extract_reasoning(response) == "step 1"
+
+    def test_anthropic_thinking_blocks(self):
+        """Test thinking content blocks are joined in order."""
+        response = _FakeResponse(
+            content=[
+                {"type": "thinking", "thinking": "First consider X."},
+                {"type": "thinking", "thinking": "Then Y."},
+                {"type": "text", "text": "The answer"},
+            ]
+        )
+
+        assert _extract_reasoning(response) == "First consider X.\nThen Y."
+
+    def test_plain_response_has_no_reasoning(self):
+        """Test a plain text response yields an empty string."""
+        assert _extract_reasoning(_FakeResponse(content="hello")) == ""
+
+    def test_whitespace_reasoning_ignored(self):
+        """Test whitespace-only reasoning is treated as absent."""
+        response = _FakeResponse(additional_kwargs={"reasoning_content": "   "})
+
+        assert _extract_reasoning(response) == ""
@@ -0,0 +1,90 @@
+"""Tests for the provider credential store."""
+
+import json
+
+from aircher.auth import AuthStore, mask_key
+
+
+class TestAuthStore:
+    """Test credential persistence and discovery."""
+
+    def test_round_trip(self, tmp_path):
+        """Stored keys survive a reload."""
+        path = tmp_path / "credentials.json"
+        store = AuthStore(store_path=path)
+        store.set_key("openai", "sk-test-1234567890abcdef")
+        store.save()
+
+        reloaded = AuthStore(store_path=path)
+        assert reloaded.get_key("openai") == "sk-test-1234567890abcdef"
+        assert reloaded.providers() == ["openai"]
+
+    def test_save_restricts_permissions(self, tmp_path):
+        """The credentials file is only readable by the owner."""
+        path = tmp_path / "credentials.json"
+        store = AuthStore(store_path=path)
+        store.set_key("anthropic", "sk-ant-1234567890abcdef")
+        store.save()
+
+        assert path.stat().st_mode & 0o777 == 0o600
+
+    def test_load_tolerates_corrupt_file(self, tmp_path):
+        """A corrupt store starts empty instead of crashing."""
+        path = tmp_path / "credentials.json"
+        path.write_text("not json")
+
+        store = AuthStore(store_path=path)
+        assert store.providers() == []
+
+    def test_discover_from_environment(self, tmp_path):
+        """Known env vars map to their providers."""
+        store = AuthStore(store_path=tmp_path / "credentials.json")
+        found = store.discover(
+            env={"OPENAI_API_KEY": "sk-env-key", "UNRELATED": "x"},
+            project_dir=tmp_path,
+        )
+        assert found == {"openai": "sk-env-key"}
+
+    def test_discover_from_dotenv(self, tmp_path):
+        """Keys in a local .env file are found, env vars taking precedence."""
+        (tmp_path / ".env").write_text(
+            "# comment\n"
+            'ANTHROPIC_API_KEY="sk-ant-dotenv"\n'
+            "OPENAI_API_KEY=sk-dotenv\n"
+        )
+        store = AuthStore(store_path=tmp_path / "credentials.json")
+        found = store.discover(
+            env={"OPENAI_API_KEY": "sk-env-wins"},
+            project_dir=tmp_path,
+        )
+        assert found == {"openai": "sk-env-wins", "anthropic": "sk-ant-dotenv"}
+
+    def test_apply_to_environment_does_not_override(self, tmp_path, monkeypatch):
+        """Existing env vars win over stored keys."""
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-existing")
+        monkeypatch.delenv("ANTHROPIC_API_KEY", raising=False)
+
+        path = tmp_path / "credentials.json"
+        path.write_text(
+            json.dumps({"openai": "sk-stored", "anthropic": "sk-ant-stored"})
+        )
+        AuthStore(store_path=path).apply_to_environment()
+
+        import os
+
+        assert os.environ["OPENAI_API_KEY"] == "sk-existing"
+        assert os.environ["ANTHROPIC_API_KEY"] == "sk-ant-stored"
+
+
+class TestMaskKey:
+    """Test key masking for display."""
+
+    def test_long_key_shows_edges_only(self):
+        """Long keys keep only a short prefix and suffix."""
+        masked = mask_key("sk-test-1234567890abcdef")
+        assert masked == "sk-tes...cdef"
+        assert "1234567890" not in masked
+
+    def test_short_key_fully_masked(self):
+        """Short keys are fully hidden."""
+        assert mask_key("short") == "***"
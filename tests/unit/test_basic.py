@@ -37,3 +37,51 @@ async def test_agent_creation():
     agent = AircherAgent()
     assert agent is not None
     assert agent.graph is not None
+
+
+def test_env_var_overrides(monkeypatch):
+    """Test AIRCHER_* env vars override settings, nested ones included."""
+    from aircher.config import Settings
+
+    monkeypatch.setenv("AIRCHER_DEFAULT_MODEL", "gpt-4o")
+    monkeypatch.setenv("AIRCHER_UI__STATUS_STYLE", "plain")
+
+    settings = Settings()
+
+    assert settings.default_model == "gpt-4o"
+    assert settings.ui.status_style == "plain"
+
+
+def test_env_var_type_validation(monkeypatch):
+    """Test a mistyped env value fails loudly instead of being ignored."""
+    from aircher.config import Settings
+
+    monkeypatch.setenv("AIRCHER_TEMPERATURE", "hot")
+
+    with pytest.raises(ValueError):
+        Settings()
+
+
+def test_effective_config():
+    """Test resolved config pairs carry live overrides, never key values."""
+    from aircher.config import effective_config
+
+    pairs = dict(effective_config(model="gpt-4o-mini", mode="read"))
+
+    assert pairs["model"] == "gpt-4o-mini"
+    assert pairs["provider"] == "openai"
+    assert pairs["mode"] == "read"
+    assert "authenticated providers" in pairs
+
+
+def test_provider_header_validation():
+    """Test malformed header names are rejected at config load."""
+    from aircher.config import Settings
+
+    settings = Settings(
+        provider_headers={"openai": {"OpenAI-Organization": "org-123"}}
+    )
+    assert settings.provider_headers["openai"]["OpenAI-Organization"] == "org-123"
+
+    with pytest.raises(ValueError, match="Invalid header name"):
+        Settings(provider_headers={"openai": {"bad header": "x"}})
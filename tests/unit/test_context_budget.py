@@ -0,0 +1,94 @@
+"""Tests for context budget allocation."""
+
+from aircher.context import (
+    ContextBudget,
+    ContextCategory,
+    ContextItemType,
+    ContextWindow,
+)
+
+
+class TestContextBudget:
+    """Test fractional window allocation."""
+
+    def test_default_allocation(self):
+        """Test default fractions split the window."""
+        budget = ContextBudget()
+        allocations = budget.allocate(100000)
+
+        assert allocations[ContextCategory.INSTRUCTIONS] == 15000
+        assert allocations[ContextCategory.RETRIEVAL] == 35000
+        assert allocations[ContextCategory.HISTORY] == 50000
+
+    def test_fractions_normalized(self):
+        """Test that non-unit fraction sums are normalized."""
+        budget = ContextBudget(
+            instructions_fraction=1.0,
+            retrieval_fraction=1.0,
+            history_fraction=2.0,
+        )
+        allocations = budget.allocate(100000)
+
+        assert allocations[ContextCategory.HISTORY] == 50000
+        assert allocations[ContextCategory.RETRIEVAL] == 25000
+
+
+class TestBudgetEnforcement:
+    """Test per-category trimming in the context window."""
+
+    def test_retrieval_trimmed_before_history(self):
+        """Test that overflowing retrieval drops tool results, not messages."""
+        window = ContextWindow(
+            session_id="test",
+            token_limit=1000,
+            budget=ContextBudget(
+                instructions_fraction=0.2,
+                retrieval_fraction=0.3,
+                history_fraction=0.5,
+            ),
+        )
+        window.add_item(
+            ContextItemType.USER_MESSAGE, {"text": "hi"}, token_cost=100
+        )
+        # 300-token retrieval allowance; the oldest result should be dropped
+        window.add_item(
+            ContextItemType.TOOL_RESULT, {"result": "old"}, token_cost=200
+        )
+        window.add_item(
+            ContextItemType.TOOL_RESULT, {"result": "new"}, token_cost=200
+        )
+
+        contents = [item.content for item in window.items]
+        assert {"result": "old"} not in contents
+        assert {"result": "new"} in contents
+        assert {"text": "hi"} in contents
+
+    def test_within_budget_untouched(self):
+        """Test that nothing is trimmed while within allocations."""
+        window = ContextWindow(session_id="test", token_limit=10000)
+        window.add_item(
+            ContextItemType.TOOL_RESULT, {"result": "a"}, token_cost=100
+        )
+        window.add_item(
+            ContextItemType.USER_MESSAGE, {"text": "hi"}, token_cost=100
+        )
+
+        assert len(window.items) == 2
+
+    def test_sticky_items_survive_budget_trim(self):
+        """Test that sticky items are never budget-trimmed."""
+        window = ContextWindow(
+            session_id="test",
+            token_limit=1000,
+            budget=ContextBudget(retrieval_fraction=0.1),
+        )
+        window.add_item(
+            ContextItemType.CODE_SNIPPET, {"code": "keep"}, token_cost=500, sticky=True
+        )
+        window.add_item(
+            ContextItemType.TOOL_RESULT, {"result": "drop"}, token_cost=500
+        )
+
+        contents = [item.content for item in window.items]
+        assert {"code": "keep"} in contents
+        assert {"result": "drop"} not in contents
@@ -0,0 +1,42 @@
+"""Tests for budget-aware context file reads."""
+
+from aircher.context import read_for_context
+
+
+class TestReadForContext:
+    """Test head/tail elision for oversized files."""
+
+    def test_small_file_returned_whole(self, tmp_path):
+        """Test that files within budget are not elided."""
+        path = tmp_path / "small.py"
+        path.write_text("line one\nline two\n")
+
+        content, elided = read_for_context(path, token_budget=100)
+
+        assert not elided
+        assert content == "line one\nline two"
+
+    def test_large_file_elided(self, tmp_path):
+        """Test that oversized files keep head and tail with a marker."""
+        path = tmp_path / "big.py"
+        lines = [f"def function_{i}(): pass" for i in range(2000)]
+        path.write_text("\n".join(lines))
+
+        content, elided = read_for_context(path, token_budget=500)
+
+        assert elided
+        assert "lines elided" in content
+        assert "function_0" in content  # head preserved
+        assert "function_1999" in content  # tail preserved
+        assert len(content) < len("\n".join(lines))
+
+    def test_budget_roughly_respected(self, tmp_path):
+        """Test that elided output stays near the token budget."""
+        path = tmp_path / "big.txt"
+        path.write_text("\n".join("x" * 80 for _ in range(1000)))
+
+        content, elided = read_for_context(path, token_budget=400)
+
+        assert elided
+        # 1 token ~ 4 chars; allow headroom for the marker line
+        assert len(content) < 400 * 4 * 1.5
@@ -0,0 +1,63 @@
+"""Tests for the git commit agent tool."""
+
+import pytest
+
+from aircher.tools import GitCommitTool
+
+
+@pytest.fixture
+def git_repo(git_repo, monkeypatch):
+    """The shared repo fixture, entered as cwd with commit identity set."""
+    monkeypatch.chdir(git_repo)
+    monkeypatch.setenv("GIT_AUTHOR_NAME", "test")
+    monkeypatch.setenv("GIT_AUTHOR_EMAIL", "test@example.com")
+    monkeypatch.setenv("GIT_COMMITTER_NAME", "test")
+    monkeypatch.setenv("GIT_COMMITTER_EMAIL", "test@example.com")
+    return git_repo
+
+
+class TestGitCommitTool:
+    """Test staging and committing through the tool."""
+
+    @pytest.mark.asyncio
+    async def test_commit_staged_files(self, git_repo):
+        """Test a change is staged and committed with the given message."""
+        (git_repo / "README.md").write_text("changed\n")
+        tool = GitCommitTool()
+
+        result = await tool.execute(files=["README.md"], message="update readme")
+
+        assert result.success
+        assert result.data["commit"]
+        assert result.data["files"] == ["README.md"]
+
+    @pytest.mark.asyncio
+    async def test_not_a_git_repo(self, tmp_path, monkeypatch):
+        """Test a clear error outside git repos."""
+        monkeypatch.chdir(tmp_path)
+        tool = GitCommitTool()
+
+        result = await tool.execute(files=["x.txt"], message="msg")
+
+        assert not result.success
+        assert "git repository" in result.error.lower()
+
+    @pytest.mark.asyncio
+    async def test_nothing_to_commit(self, git_repo):
+        """Test git's own explanation surfaces when there are no changes."""
+        tool = GitCommitTool()
+
+        result = await tool.execute(files=["README.md"], message="noop")
+
+        assert not result.success
+        assert "git commit failed" in result.error
+
+    @pytest.mark.asyncio
+    async def test_path_traversal_rejected(self, git_repo):
+        """Test files outside the working directory are refused."""
+        tool = GitCommitTool()
+
+        result = await tool.execute(files=["../outside.txt"], message="msg")
+
+        assert not result.success
+        assert "Access denied" in result.error
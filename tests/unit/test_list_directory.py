@@ -0,0 +1,90 @@
+"""Tests for gitignore-aware directory listing."""
+
+import pytest
+
+from aircher.tools.file_ops import IgnoreMatcher, ListDirectoryTool
+
+
+@pytest.fixture
+def project(tmp_path, monkeypatch):
+    """A small project tree with ignored and tracked files."""
+    (tmp_path / ".gitignore").write_text("*.log\nbuild/\n/secrets.txt\n")
+    (tmp_path / "main.py").write_text("print('hi')\n")
+    (tmp_path / "debug.log").write_text("noise\n")
+    (tmp_path / "secrets.txt").write_text("hush\n")
+    (tmp_path / "build").mkdir()
+    (tmp_path / "build" / "out.bin").write_text("binary\n")
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "app.py").write_text("pass\n")
+    (tmp_path / "src" / "trace.log").write_text("noise\n")
+    monkeypatch.chdir(tmp_path)
+    return tmp_path
+
+
+class TestIgnoreMatcher:
+    """Test .gitignore pattern handling."""
+
+    def test_glob_pattern_matches_anywhere(self, project):
+        """Test *.log matches at any depth."""
+        matcher = IgnoreMatcher(project)
+        assert matcher.is_ignored("debug.log", is_dir=False)
+        assert matcher.is_ignored("src/trace.log", is_dir=False)
+        assert not matcher.is_ignored("main.py", is_dir=False)
+
+    def test_dir_only_pattern_covers_contents(self, project):
+        """Test build/ ignores the directory and everything in it."""
+        matcher = IgnoreMatcher(project)
+        assert matcher.is_ignored("build", is_dir=True)
+        assert matcher.is_ignored("build/out.bin", is_dir=False)
+
+    def test_anchored_pattern_only_matches_root(self, project):
+        """Test /secrets.txt only matches at the root."""
+        matcher = IgnoreMatcher(project)
+        assert matcher.is_ignored("secrets.txt", is_dir=False)
+        assert not matcher.is_ignored("src/secrets.txt", is_dir=False)
+
+    def test_always_ignored_directories(self, tmp_path):
+        """Test .git and friends are skipped even without a .gitignore."""
+        matcher = IgnoreMatcher(tmp_path)
+        assert matcher.is_ignored("node_modules/left-pad/index.js", is_dir=False)
+
+
+class TestListDirectoryTool:
+    """Test the listing tool's ignore and glob behavior."""
+
+    @pytest.mark.asyncio
+    async def test_ignored_files_skipped_by_default(self, project):
+        """Test gitignored entries don't appear in listings."""
+        result = await ListDirectoryTool().execute(path=".", recursive=True)
+
+        assert result.success
+        names = {item["name"] for item in result.data}
+        assert "main.py" in names
+        assert "app.py" in names
+        assert "debug.log" not in names
+        assert "build" not in names
+
+    @pytest.mark.asyncio
+    async def test_include_ignored_restores_entries(self, project):
+        """Test include_ignored lists everything again."""
+        result = await ListDirectoryTool().execute(
+            path=".", recursive=True, include_ignored=True
+        )
+
+        assert result.success
+        names = {item["name"] for item in result.data}
+        assert "debug.log" in names
+        assert "out.bin" in names
+        assert not result.metadata["ignores_respected"]
+
+    @pytest.mark.asyncio
+    async def test_glob_filters_files_not_directories(self, project):
+        """Test a glob narrows files while directories stay traversable."""
+        result = await ListDirectoryTool().execute(
+            path=".", recursive=True, glob="*.py"
+        )
+
+        assert result.success
+        files = {i["name"] for i in result.data if i["type"] == "file"}
+        assert files == {"main.py", "app.py"}
+        assert any(i["type"] == "directory" for i in result.data)
@@ -1,5 +1,7 @@
 """Tests for model router."""
 
+from types import SimpleNamespace
+
 import pytest
 
 from aircher.models import (
@@ -7,6 +9,7 @@ from aircher.models import (
     ModelProvider,
     ModelRouter,
     ModelTier,
+    rate_limit_delay,
     SessionCostTracker,
     SUPPORTED_MODELS,
 )
@@ -92,6 +95,55 @@ class TestSessionCostTracker:
         assert summary["call_count"] == 2
         assert "gpt-4o-mini" in summary["models_used"]
 
+    def test_cached_input_tokens_discounted(self):
+        """Test cache reads cost less and savings are reported."""
+        tracker = SessionCostTracker(session_id="test")
+
+        full = tracker.add_usage("gpt-4o", input_tokens=10000, output_tokens=0)
+        tracker = SessionCostTracker(session_id="test2")
+        cached = tracker.add_usage(
+            "gpt-4o", input_tokens=10000, output_tokens=0, cached_input_tokens=8000
+        )
+
+        assert cached.estimated_cost < full.estimated_cost
+        assert cached.cache_savings > 0
+        summary = tracker.get_summary()
+        assert summary["total_cached_input_tokens"] == 8000
+        assert summary["cache_savings"] == round(cached.cache_savings, 4)
+
+    def test_cache_discount_is_per_provider(self):
+        """Test OpenAI's 50% cache rate isn't billed at Anthropic's 90%."""
+        tracker = SessionCostTracker(session_id="test")
+
+        openai = tracker.add_usage(
+            "gpt-4o", input_tokens=10000, output_tokens=0, cached_input_tokens=10000
+        )
+        anthropic = tracker.add_usage(
+            "claude-sonnet-4-20250514",
+            input_tokens=10000,
+            output_tokens=0,
+            cached_input_tokens=10000,
+        )
+
+        input_price = SUPPORTED_MODELS["gpt-4o"].cost_per_1k_input
+        assert openai.cache_savings == pytest.approx(10 * input_price * 0.5)
+        input_price = SUPPORTED_MODELS["claude-sonnet-4-20250514"].cost_per_1k_input
+        assert anthropic.cache_savings == pytest.approx(10 * input_price * 0.9)
+
+    def test_get_summary_per_model_breakdown(self):
+        """Test the per-model usage buckets in the summary."""
+        tracker = SessionCostTracker(session_id="test")
+
+        tracker.add_usage("gpt-4o-mini", input_tokens=1000, output_tokens=500)
+        tracker.add_usage("gpt-4o", input_tokens=2000, output_tokens=1000)
+
+        by_model = tracker.get_summary()["by_model"]
+
+        assert by_model["gpt-4o-mini"]["input_tokens"] == 1000
+        assert by_model["gpt-4o-mini"]["calls"] == 1
+        assert by_model["gpt-4o"]["output_tokens"] == 1000
+        assert by_model["gpt-4o"]["cost"] > by_model["gpt-4o-mini"]["cost"]
+
 
 class TestModelRouter:
     """Test model router."""
@@ -137,6 +189,25 @@ class TestModelRouter:
         assert summary["total_cost"] > 0
         assert summary["total_tokens"] == 1500
 
+    def test_track_usage_forwards_reasoning_and_cache(self):
+        """Test reasoning and cached-token counts reach the cost tracker."""
+        router = ModelRouter(session_id="test")
+
+        usage = router.track_usage(
+            "gpt-4o",
+            input_tokens=10000,
+            output_tokens=500,
+            reasoning_tokens=200,
+            cached_input_tokens=8000,
+        )
+
+        assert usage.reasoning_tokens == 200
+        assert usage.cached_input_tokens == 8000
+        assert usage.cache_savings > 0
+
+        summary = router.get_cost_summary()
+        assert summary["cache_savings"] > 0
+
     def test_get_model_openai(self):
         """Test getting an OpenAI model."""
         router = ModelRouter(default_model="gpt-4o-mini")
@@ -165,3 +236,417 @@ class TestModelRouter:
             current_tier_order = prev_tier_order[config.tier.value]
             assert current_tier_order >= prev_tier
             prev_tier = current_tier_order
+
+
+class TestUtilityModelRouting:
+    """Test utility-model override for housekeeping tasks."""
+
+    def test_utility_model_used_for_summarization(self, monkeypatch):
+        """Test that a configured utility model wins for utility tasks."""
+        from aircher.config import get_settings
+
+        monkeypatch.setenv("AIRCHER_UTILITY_MODEL", "gpt-4o-mini")
+        get_settings.cache_clear()
+        try:
+            router = ModelRouter(default_model="gpt-4o")
+            assert router.select_model_for_task("summarization") == "gpt-4o-mini"
+        finally:
+            get_settings.cache_clear()
+
+    def test_unset_utility_model_falls_through(self, monkeypatch):
+        """Test tier routing applies when no utility model is configured."""
+        from aircher.config import get_settings
+
+        monkeypatch.delenv("AIRCHER_UTILITY_MODEL", raising=False)
+        get_settings.cache_clear()
+        try:
+            router = ModelRouter(default_model="gpt-4o")
+            model = router.select_model_for_task("summarization")
+            assert SUPPORTED_MODELS[model].tier == ModelTier.SMALL
+        finally:
+            get_settings.cache_clear()
+
+
+class TestProviderHealth:
+    """Test health-aware fallback selection."""
+
+    def test_failure_triggers_cooldown(self):
+        """Test a failed provider is unhealthy until the cooldown elapses."""
+        from aircher.models import ModelProvider, ProviderHealth
+
+        health = ProviderHealth(cooldown_seconds=60.0)
+        assert health.is_healthy(ModelProvider.OPENAI)
+
+        health.record_failure(ModelProvider.OPENAI)
+        assert not health.is_healthy(ModelProvider.OPENAI)
+        assert health.is_healthy(ModelProvider.ANTHROPIC)
+
+    def test_success_clears_failure(self):
+        """Test a success resets the cooldown immediately."""
+        from aircher.models import ModelProvider, ProviderHealth
+
+        health = ProviderHealth(cooldown_seconds=60.0)
+        health.record_failure(ModelProvider.OPENAI)
+        health.record_success(ModelProvider.OPENAI)
+
+        assert health.is_healthy(ModelProvider.OPENAI)
+
+    def test_cooldown_expires(self, monkeypatch):
+        """Test the provider becomes eligible once the window passes."""
+        import aircher.models as models
+        from aircher.models import ModelProvider, ProviderHealth
+
+        clock = {"now": 0.0}
+        monkeypatch.setattr(models.time, "monotonic", lambda: clock["now"])
+
+        health = ProviderHealth(cooldown_seconds=60.0)
+        health.record_failure(ModelProvider.OPENAI)
+        assert not health.is_healthy(ModelProvider.OPENAI)
+
+        clock["now"] = 61.0
+        assert health.is_healthy(ModelProvider.OPENAI)
+
+    def test_authentication_check(self, monkeypatch):
+        """Test local providers never need credentials; cloud ones do."""
+        from aircher.models import ModelProvider, provider_is_authenticated
+
+        monkeypatch.delenv("OPENROUTER_API_KEY", raising=False)
+        assert provider_is_authenticated(ModelProvider.OLLAMA)
+        assert not provider_is_authenticated(ModelProvider.OPENROUTER)
+
+        monkeypatch.setenv("OPENROUTER_API_KEY", "sk-test")
+        assert provider_is_authenticated(ModelProvider.OPENROUTER)
+
+
+class TestLatencyMonitor:
+    """Test slow-model detection and downgrade suggestions."""
+
+    def test_consecutive_slow_triggers_downgrade(self):
+        """Test N consecutive slow requests make a downgrade due."""
+        from aircher.models import LatencyMonitor
+
+        monitor = LatencyMonitor(slow_seconds=10.0, threshold=3)
+        monitor.record("gpt-4o", 15.0)
+        monitor.record("gpt-4o", 20.0)
+        assert not monitor.downgrade_due("gpt-4o")
+
+        monitor.record("gpt-4o", 12.0)
+        assert monitor.downgrade_due("gpt-4o")
+        assert monitor.consecutive_slow("gpt-4o") == 3
+
+    def test_fast_request_resets_streak(self):
+        """Test a fast request clears the slow streak."""
+        from aircher.models import LatencyMonitor
+
+        monitor = LatencyMonitor(slow_seconds=10.0, threshold=2)
+        monitor.record("gpt-4o", 15.0)
+        monitor.record("gpt-4o", 2.0)
+        monitor.record("gpt-4o", 15.0)
+        assert not monitor.downgrade_due("gpt-4o")
+
+    def test_failures_count_as_slow(self):
+        """Test outright failures count toward the streak."""
+        from aircher.models import LatencyMonitor
+
+        monitor = LatencyMonitor(slow_seconds=10.0, threshold=2)
+        monitor.record("gpt-4o", 1.0, failed=True)
+        monitor.record("gpt-4o", 1.0, failed=True)
+        assert monitor.downgrade_due("gpt-4o")
+
+    def test_disabled_when_slow_seconds_zero(self):
+        """Test zero slow_seconds disables tracking."""
+        from aircher.models import LatencyMonitor
+
+        monitor = LatencyMonitor(slow_seconds=0.0, threshold=1)
+        monitor.record("gpt-4o", 100.0)
+        assert not monitor.downgrade_due("gpt-4o")
+
+
+class TestSuggestDowngrade:
+    """Test the faster-model suggestion."""
+
+    def test_same_provider_smaller_tier(self):
+        """Test medium models suggest a small model from the same provider."""
+        from aircher.models import suggest_downgrade
+
+        target = suggest_downgrade("claude-sonnet-4-20250514")
+        assert target is not None
+        assert SUPPORTED_MODELS[target].provider == ModelProvider.ANTHROPIC
+        assert SUPPORTED_MODELS[target].tier == ModelTier.SMALL
+
+    def test_override_mapping_wins(self):
+        """Test an explicit mapping beats the tier-based default."""
+        from aircher.models import suggest_downgrade
+
+        target = suggest_downgrade(
+            "gpt-4o", overrides={"gpt-4o": "claude-haiku-4-20250514"}
+        )
+        assert target == "claude-haiku-4-20250514"
+
+    def test_unknown_model_returns_none(self):
+        """Test unknown models produce no suggestion."""
+        from aircher.models import suggest_downgrade
+
+        assert suggest_downgrade("not-a-model") is None
+
+class TestClampOutputTokens:
+    """Test clamping max_tokens to the model's output cap."""
+
+    def test_over_cap_is_clamped(self):
+        """Test a request above the model's cap is reduced to the cap."""
+        router = ModelRouter()
+        config = SUPPORTED_MODELS["claude-haiku-4-20250514"]
+
+        clamped = router._clamp_output_tokens(
+            "claude-haiku-4-20250514", config, 100000
+        )
+
+        assert clamped == config.max_output_tokens
+
+    def test_under_cap_passes_through(self):
+        """Test values within the cap (and None) are unchanged."""
+        router = ModelRouter()
+        config = SUPPORTED_MODELS["gpt-4o-mini"]
+
+        assert router._clamp_output_tokens("gpt-4o-mini", config, 1024) == 1024
+        assert router._clamp_output_tokens("gpt-4o-mini", config, None) is None
+
+    def test_warning_recorded_once_per_model(self):
+        """Test the clamp warning fires once per model, not per request."""
+        router = ModelRouter()
+        config = SUPPORTED_MODELS["gpt-4o-mini"]
+
+        router._clamp_output_tokens("gpt-4o-mini", config, 999999)
+        router._clamp_output_tokens("gpt-4o-mini", config, 999999)
+
+        assert router._clamp_warned == {"gpt-4o-mini"}
+
+class TestStreamCostWatcher:
+    """Test mid-stream budget threshold warnings."""
+
+    def test_warns_once_at_eighty_percent(self):
+        """Test the 80% warning fires once as output accumulates."""
+        from aircher.models import StreamCostWatcher
+
+        watcher = StreamCostWatcher("gpt-4o", budget=0.01, spent=0.0075)
+
+        assert watcher.note_output(40) is None
+        warning = watcher.note_output(400)
+        assert warning is not None and "80%" in warning
+        assert watcher.note_output(40) is None
+
+    def test_hard_stop_at_budget(self):
+        """Test crossing 100% with hard_stop asks the caller to stop."""
+        from aircher.models import StreamCostWatcher
+
+        watcher = StreamCostWatcher(
+            "gpt-4o", budget=0.01, spent=0.0099, hard_stop=True
+        )
+
+        warning = watcher.note_output(4000)
+        assert warning is not None and "stopping" in warning
+        assert watcher.stop
+
+    def test_disabled_without_budget(self):
+        """Test a zero budget never warns."""
+        from aircher.models import StreamCostWatcher
+
+        watcher = StreamCostWatcher("gpt-4o", budget=0.0)
+
+        assert watcher.note_output(10**7) is None
+        assert not watcher.stop
+
+class TestContextLengthClassification:
+    """Test detecting context-overflow errors from provider messages."""
+
+    def test_known_phrasings_match(self):
+        """Test common provider wordings are classified as context overflow."""
+        from aircher.models import is_context_length_error
+
+        assert is_context_length_error(
+            Exception("Error code: 400 - context_length_exceeded")
+        )
+        assert is_context_length_error(
+            Exception("prompt is too long: 210000 tokens > 200000 maximum")
+        )
+
+    def test_other_bad_requests_do_not_match(self):
+        """Test unrelated errors are not treated as recoverable overflow."""
+        from aircher.models import is_context_length_error
+
+        assert not is_context_length_error(Exception("invalid api key"))
+        assert not is_context_length_error(Exception("rate limit exceeded"))
+
+class TestModelForTask:
+    """Test the central per-task model resolver."""
+
+    def test_explicit_task_mapping_wins(self, monkeypatch):
+        """Test a task_models entry beats utility and tier routing."""
+        from aircher.config import get_settings
+        from aircher.models import model_for_task
+
+        monkeypatch.setenv("AIRCHER_TASK_MODELS", '{"summarization": "gpt-4o"}')
+        monkeypatch.setenv("AIRCHER_UTILITY_MODEL", "gpt-4o-mini")
+        get_settings.cache_clear()
+        try:
+            assert model_for_task("summarization") == "gpt-4o"
+        finally:
+            get_settings.cache_clear()
+
+    def test_unknown_mapped_model_ignored(self, monkeypatch):
+        """Test a mapping to an unsupported model falls through."""
+        from aircher.config import get_settings
+        from aircher.models import model_for_task
+
+        monkeypatch.setenv("AIRCHER_TASK_MODELS", '{"chat": "not-a-model"}')
+        get_settings.cache_clear()
+        try:
+            model = model_for_task("chat", default_model="gpt-4o-mini")
+            assert model in SUPPORTED_MODELS
+        finally:
+            get_settings.cache_clear()
+
+    def test_heuristic_can_be_disabled(self, monkeypatch):
+        """Test callers can skip tier routing and keep their own default."""
+        from aircher.config import get_settings
+        from aircher.models import model_for_task
+
+        monkeypatch.delenv("AIRCHER_TASK_MODELS", raising=False)
+        monkeypatch.delenv("AIRCHER_UTILITY_MODEL", raising=False)
+        get_settings.cache_clear()
+        try:
+            model = model_for_task(
+                "summarization", default_model="gpt-4o", use_tier_heuristic=False
+            )
+            assert model == "gpt-4o"
+        finally:
+            get_settings.cache_clear()
+
+
+class TestReasoningKwargs:
+    """Test effort translation into provider-specific kwargs."""
+
+    def _config(self, provider, supports_reasoning=True):
+        return ModelConfig(
+            name="test-model",
+            provider=provider,
+            tier=ModelTier.MEDIUM,
+            cost_per_1k_input=0.001,
+            cost_per_1k_output=0.002,
+            context_window=128000,
+            supports_reasoning=supports_reasoning,
+        )
+
+    def test_openai_named_effort(self):
+        """Test OpenAI gets a named reasoning_effort parameter."""
+        from aircher.models import reasoning_kwargs
+
+        kwargs = reasoning_kwargs(self._config(ModelProvider.OPENAI), "high")
+
+        assert kwargs == {"model_kwargs": {"reasoning_effort": "high"}}
+
+    def test_anthropic_thinking_budget(self):
+        """Test Anthropic gets an explicit thinking-token budget."""
+        from aircher.models import reasoning_kwargs
+
+        kwargs = reasoning_kwargs(self._config(ModelProvider.ANTHROPIC), "low")
+
+        assert kwargs["thinking"]["type"] == "enabled"
+        assert kwargs["thinking"]["budget_tokens"] > 0
+
+    def test_non_reasoning_model_ignored(self):
+        """Test models without reasoning support get nothing."""
+        from aircher.models import reasoning_kwargs
+
+        config = self._config(ModelProvider.OPENAI, supports_reasoning=False)
+
+        assert reasoning_kwargs(config, "high") == {}
+
+    def test_unknown_effort_ignored(self):
+        """Test an unrecognized effort level is dropped, not sent."""
+        from aircher.models import reasoning_kwargs
+
+        assert reasoning_kwargs(self._config(ModelProvider.OPENAI), "max") == {}
+
+
+class TestAuthFingerprint:
+    """Test the client-cache auth fingerprint."""
+
+    def test_stable_for_unchanged_config(self, monkeypatch):
+        """Test the same key and headers fingerprint identically."""
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-test-123456")
+        router = ModelRouter(enable_fallback=False)
+
+        first = router._auth_fingerprint(ModelProvider.OPENAI, {"X-Org": "a"})
+        second = router._auth_fingerprint(ModelProvider.OPENAI, {"X-Org": "a"})
+
+        assert first == second
+
+    def test_changed_key_changes_fingerprint(self, monkeypatch):
+        """Test rotating the API key invalidates the cached client."""
+        router = ModelRouter(enable_fallback=False)
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-test-123456")
+        first = router._auth_fingerprint(ModelProvider.OPENAI, {})
+
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-test-654321")
+        second = router._auth_fingerprint(ModelProvider.OPENAI, {})
+
+        assert first != second
+
+    def test_changed_headers_change_fingerprint(self, monkeypatch):
+        """Test edited provider headers invalidate the cached client."""
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-test-123456")
+        router = ModelRouter(enable_fallback=False)
+
+        first = router._auth_fingerprint(ModelProvider.OPENAI, {"X-Org": "a"})
+        second = router._auth_fingerprint(ModelProvider.OPENAI, {"X-Org": "b"})
+
+        assert first != second
+
+    def test_fingerprint_never_contains_key(self, monkeypatch):
+        """Test the fingerprint is a hash, not the raw secret."""
+        monkeypatch.setenv("OPENAI_API_KEY", "sk-test-123456")
+        router = ModelRouter(enable_fallback=False)
+
+        fingerprint = router._auth_fingerprint(ModelProvider.OPENAI, {})
+
+        assert "sk-test-123456" not in fingerprint
+
+
+class TestRateLimitDelay:
+    """Test Retry-After extraction from provider errors."""
+
+    def _error(self, message="Rate limit exceeded (429)", headers=None):
+        error = Exception(message)
+        if headers is not None:
+            error.response = SimpleNamespace(headers=headers)
+        return error
+
+    def test_retry_after_seconds(self):
+        """Test a numeric Retry-After header becomes a delay."""
+        error = self._error(headers={"retry-after": "23"})
+
+        assert rate_limit_delay(error) == 23.0
+
+    def test_retry_after_ms_preferred(self):
+        """Test retry-after-ms is converted from milliseconds."""
+        error = self._error(headers={"retry-after-ms": "1500"})
+
+        assert rate_limit_delay(error) == 1.5
+
+    def test_capped_at_max(self):
+        """Test absurd reset times are capped."""
+        error = self._error(headers={"retry-after": "3600"})
+
+        assert rate_limit_delay(error, max_seconds=120.0) == 120.0
+
+    def test_non_rate_limit_error_ignored(self):
+        """Test unrelated errors never produce a delay."""
+        error = self._error(message="connection reset", headers={"retry-after": "5"})
+
+        assert rate_limit_delay(error) is None
+
+    def test_rate_limit_without_header(self):
+        """Test a 429 without a reset header stays a normal error."""
+        assert rate_limit_delay(self._error(headers={})) is None
+        assert rate_limit_delay(self._error()) is None
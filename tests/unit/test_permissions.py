@@ -0,0 +1,55 @@
+"""Tests for approved-command permission rules."""
+
+from aircher.permissions import PermissionsManager
+
+
+class TestPermissionsManager:
+    """Test permission rule storage and matching."""
+
+    def test_session_approval_matches(self, tmp_path):
+        """Test that approved patterns match commands via globs."""
+        manager = PermissionsManager(rules_path=tmp_path / "permissions.json")
+        manager.approve("git *", scope="session")
+
+        assert manager.is_approved("git status")
+        assert not manager.is_approved("rm -rf /")
+
+    def test_always_scope_persists(self, tmp_path):
+        """Test that always-scoped rules survive a restart."""
+        rules_path = tmp_path / "permissions.json"
+        PermissionsManager(rules_path=rules_path).approve("npm run *", scope="always")
+
+        reloaded = PermissionsManager(rules_path=rules_path)
+        assert reloaded.is_approved("npm run build")
+
+    def test_session_scope_does_not_persist(self, tmp_path):
+        """Test that session rules are gone after a restart."""
+        rules_path = tmp_path / "permissions.json"
+        PermissionsManager(rules_path=rules_path).approve("git *", scope="session")
+
+        reloaded = PermissionsManager(rules_path=rules_path)
+        assert not reloaded.is_approved("git status")
+
+    def test_remove_rule(self, tmp_path):
+        """Test removing a persistent rule."""
+        rules_path = tmp_path / "permissions.json"
+        manager = PermissionsManager(rules_path=rules_path)
+        manager.approve("git *", scope="always")
+
+        assert manager.remove("git *")
+        assert not manager.is_approved("git status")
+        assert not PermissionsManager(rules_path=rules_path).is_approved("git status")
+
+    def test_remove_missing_rule(self, tmp_path):
+        """Test that removing an unknown pattern reports failure."""
+        manager = PermissionsManager(rules_path=tmp_path / "permissions.json")
+
+        assert not manager.remove("does-not-exist")
+
+    def test_corrupt_rules_file_ignored(self, tmp_path):
+        """Test that a corrupt rules file yields no rules."""
+        rules_path = tmp_path / "permissions.json"
+        rules_path.write_text("{not json")
+
+        manager = PermissionsManager(rules_path=rules_path)
+        assert manager.list_rules() == []
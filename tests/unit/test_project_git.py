@@ -0,0 +1,64 @@
+"""Tests for project git-status reading."""
+
+from aircher.project import GitStatusCache, changed_files, read_git_status
+
+
+class TestGitStatus:
+    """Test git state detection."""
+
+    def test_non_git_directory(self, tmp_path):
+        """Test that non-git directories degrade to None."""
+        assert read_git_status(tmp_path) is None
+
+    def test_clean_repo(self, git_repo):
+        """Test branch detection on a clean tree."""
+        status = read_git_status(git_repo)
+
+        assert status is not None
+        assert status.branch == "main"
+        assert not status.dirty
+        assert "clean" in status.summary()
+
+    def test_dirty_repo(self, git_repo):
+        """Test uncommitted changes are reported."""
+        (git_repo / "README.md").write_text("changed\n")
+
+        status = read_git_status(git_repo)
+
+        assert status is not None
+        assert status.dirty
+        assert "README.md" in status.changed_files
+        assert "uncommitted" in status.summary()
+
+    def test_changed_files_non_git(self, tmp_path):
+        """Test non-git directories return None for fallback handling."""
+        assert changed_files(tmp_path) is None
+
+    def test_changed_files_lists_edits_untracked_and_deletions(self, git_repo, git):
+        """Test modified, untracked, and deleted paths are all reported."""
+        (git_repo / "README.md").write_text("changed\n")
+        (git_repo / "new.py").write_text("x = 1\n")
+        (git_repo / "gone.py").write_text("y = 2\n")
+        git(git_repo, "add", "gone.py")
+        git(git_repo, "commit", "-m", "add gone")
+        (git_repo / "gone.py").unlink()
+
+        names = changed_files(git_repo)
+
+        assert names is not None
+        assert "README.md" in names
+        assert "new.py" in names
+        assert "gone.py" in names
+
+    def test_cache_serves_stale_within_ttl(self, git_repo):
+        """Test that the cache avoids re-reading within the TTL."""
+        cache = GitStatusCache(git_repo, ttl_seconds=60.0)
+        first = cache.get()
+        (git_repo / "README.md").write_text("changed\n")
+
+        assert cache.get() == first
+
+        cache.invalidate()
+        refreshed = cache.get()
+        assert refreshed is not None
+        assert refreshed.dirty
@@ -0,0 +1,41 @@
+"""Tests for agent-instruction file detection."""
+
+from aircher.project import read_agent_instructions
+
+
+class TestAgentInstructions:
+    """Test loading instruction files from the project root."""
+
+    def test_agent_md_wins(self, tmp_path):
+        """Test AGENT.md overrides other tools' instruction files."""
+        (tmp_path / "AGENT.md").write_text("ours\n")
+        (tmp_path / "CLAUDE.md").write_text("theirs\n")
+
+        assert read_agent_instructions(tmp_path) == ("AGENT.md", "ours")
+
+    def test_other_tool_files_honored(self, tmp_path):
+        """Test a lone .cursorrules is picked up."""
+        (tmp_path / ".cursorrules").write_text("be terse\n")
+
+        assert read_agent_instructions(tmp_path) == (".cursorrules", "be terse")
+
+    def test_nested_copilot_file(self, tmp_path):
+        """Test the .github copilot instructions path is checked."""
+        github = tmp_path / ".github"
+        github.mkdir()
+        (github / "copilot-instructions.md").write_text("review hard\n")
+
+        result = read_agent_instructions(tmp_path)
+
+        assert result == (".github/copilot-instructions.md", "review hard")
+
+    def test_empty_files_skipped(self, tmp_path):
+        """Test blank files fall through to the next candidate."""
+        (tmp_path / "AGENT.md").write_text("\n")
+        (tmp_path / "CLAUDE.md").write_text("theirs\n")
+
+        assert read_agent_instructions(tmp_path) == ("CLAUDE.md", "theirs")
+
+    def test_no_instruction_files(self, tmp_path):
+        """Test a project without instruction files yields None."""
+        assert read_agent_instructions(tmp_path) is None
@@ -0,0 +1,60 @@
+"""Tests for project primary-language detection."""
+
+from aircher.project import detect_languages, detect_primary_language
+
+
+class TestPrimaryLanguage:
+    """Test extension-based language guessing."""
+
+    def test_majority_extension_wins(self, tmp_path):
+        """Test the most common recognized extension is chosen."""
+        (tmp_path / "a.py").write_text("x = 1\n")
+        (tmp_path / "b.py").write_text("y = 2\n")
+        (tmp_path / "c.rs").write_text("fn main() {}\n")
+
+        assert detect_primary_language(tmp_path) == "python"
+
+    def test_hidden_directories_skipped(self, tmp_path):
+        """Test files under dot-directories don't count."""
+        hidden = tmp_path / ".venv"
+        hidden.mkdir()
+        (hidden / "mod.rs").write_text("fn main() {}\n")
+        (tmp_path / "a.py").write_text("x = 1\n")
+
+        assert detect_primary_language(tmp_path) == "python"
+
+    def test_unrecognizable_project(self, tmp_path):
+        """Test no recognized files returns None."""
+        (tmp_path / "notes.txt").write_text("hello\n")
+
+        assert detect_primary_language(tmp_path) is None
+
+    def test_marker_file_breaks_the_tie(self, tmp_path):
+        """Test a root manifest answers when no source files exist."""
+        (tmp_path / "pyproject.toml").write_text("[project]\n")
+
+        assert detect_primary_language(tmp_path) == "python"
+
+
+class TestDetectLanguages:
+    """Test multi-language detection for polyglot projects."""
+
+    def test_markers_and_dominant_extensions_union(self, tmp_path):
+        """Test marker languages join significant extension languages."""
+        (tmp_path / "Cargo.toml").write_text("[package]\n")
+        (tmp_path / "app.ts").write_text("const x = 1;\n")
+        (tmp_path / "lib.ts").write_text("const y = 2;\n")
+
+        assert detect_languages(tmp_path) == {"rust", "typescript"}
+
+    def test_minority_language_excluded(self, tmp_path):
+        """Test a language below the share threshold doesn't count."""
+        for i in range(9):
+            (tmp_path / f"m{i}.py").write_text("x = 1\n")
+        (tmp_path / "one.rs").write_text("fn main() {}\n")
+
+        assert detect_languages(tmp_path) == {"python"}
+
+    def test_empty_project(self, tmp_path):
+        """Test nothing recognizable yields an empty set."""
+        assert detect_languages(tmp_path) == set()
@@ -0,0 +1,47 @@
+"""Tests for durable per-project notes."""
+
+from aircher.project import ProjectNotes
+
+
+class TestProjectNotes:
+    """Test note persistence and prompt rendering."""
+
+    def test_add_persists_across_instances(self, tmp_path):
+        """Test notes survive a restart."""
+        ProjectNotes(tmp_path).add("the API uses snake_case")
+
+        reloaded = ProjectNotes(tmp_path)
+        assert [n.note for n in reloaded.notes] == ["the API uses snake_case"]
+
+    def test_remove(self, tmp_path):
+        """Test removing a note by index."""
+        notes = ProjectNotes(tmp_path)
+        notes.add("first")
+        notes.add("second")
+
+        assert notes.remove(0)
+        assert [n.note for n in notes.notes] == ["second"]
+        assert not notes.remove(5)
+
+    def test_render_empty(self, tmp_path):
+        """Test no notes renders nothing."""
+        assert ProjectNotes(tmp_path).render() == ""
+
+    def test_render_caps_size_dropping_oldest(self, tmp_path):
+        """Test the injection cap drops the oldest notes first."""
+        notes = ProjectNotes(tmp_path)
+        notes.add("old " + "x" * 100)
+        notes.add("new " + "y" * 100)
+
+        rendered = notes.render(max_chars=150)
+
+        assert "new" in rendered
+        assert "old" not in rendered
+
+    def test_corrupt_file_ignored(self, tmp_path):
+        """Test a corrupt notes file yields no notes."""
+        path = tmp_path / ".aircher"
+        path.mkdir()
+        (path / "notes.json").write_text("{broken")
+
+        assert ProjectNotes(tmp_path).notes == []
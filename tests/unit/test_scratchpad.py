@@ -0,0 +1,70 @@
+"""Tests for the scratchpad tool."""
+
+import pytest
+
+from aircher.tools.scratchpad import ScratchpadTool, scratchpad_path
+
+
+class TestScratchpadTool:
+    """Test the sandboxed working-notes file."""
+
+    @pytest.mark.asyncio
+    async def test_read_before_write_is_empty(self, tmp_path):
+        """Test reading a missing scratchpad succeeds with empty content."""
+        tool = ScratchpadTool(project_dir=tmp_path)
+
+        result = await tool.execute(action="read")
+
+        assert result.success
+        assert result.data == ""
+        assert result.metadata["empty"]
+
+    @pytest.mark.asyncio
+    async def test_write_then_read(self, tmp_path):
+        """Test write creates .aircher/scratch.md and read returns it."""
+        tool = ScratchpadTool(project_dir=tmp_path)
+
+        write = await tool.execute(action="write", content="# Plan\n- step 1\n")
+        read = await tool.execute(action="read")
+
+        assert write.success
+        assert read.data == "# Plan\n- step 1\n"
+        assert scratchpad_path(tmp_path).exists()
+
+    @pytest.mark.asyncio
+    async def test_append_extends_notes(self, tmp_path):
+        """Test append keeps earlier entries and separates them by newline."""
+        tool = ScratchpadTool(project_dir=tmp_path)
+        await tool.execute(action="write", content="step 1 done")
+
+        await tool.execute(action="append", content="step 2 done")
+        read = await tool.execute(action="read")
+
+        assert read.data == "step 1 done\nstep 2 done"
+
+    @pytest.mark.asyncio
+    async def test_clear_removes_file(self, tmp_path):
+        """Test clear deletes the scratchpad file."""
+        tool = ScratchpadTool(project_dir=tmp_path)
+        await tool.execute(action="write", content="notes")
+
+        result = await tool.execute(action="clear")
+
+        assert result.success
+        assert not scratchpad_path(tmp_path).exists()
+
+    @pytest.mark.asyncio
+    async def test_write_requires_content(self, tmp_path):
+        """Test write without content is an error, not an empty file."""
+        result = await ScratchpadTool(project_dir=tmp_path).execute(action="write")
+
+        assert not result.success
+        assert "content" in result.error
+
+    @pytest.mark.asyncio
+    async def test_unknown_action_rejected(self, tmp_path):
+        """Test an unrecognized action is rejected."""
+        result = await ScratchpadTool(project_dir=tmp_path).execute(action="delete")
+
+        assert not result.success
+        assert "delete" in result.error
@@ -0,0 +1,152 @@
+"""Tests for SQLite session storage."""
+
+from datetime import datetime, timedelta
+
+from aircher.protocol import ACPSession, AgentMode
+from aircher.sessions import SessionStorage
+
+
+def make_session(session_id: str, project: str, last_activity: datetime) -> ACPSession:
+    """Build a session for storage tests."""
+    return ACPSession(
+        id=session_id,
+        created_at=last_activity,
+        last_activity=last_activity,
+        mode=AgentMode.READ,
+        project=project,
+    )
+
+
+class TestSessionStorage:
+    """Test session persistence."""
+
+    def test_session_round_trip(self, tmp_path):
+        """Test storing and loading a session with its project."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        session = make_session("s1", "/repo/a", datetime.now())
+
+        assert storage.create_session(session)
+        loaded = storage.get_session("s1")
+
+        assert loaded is not None
+        assert loaded.id == "s1"
+        assert loaded.project == "/repo/a"
+
+    def test_most_recent_for_project(self, tmp_path):
+        """Test that the latest session for the project wins."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        now = datetime.now()
+        storage.create_session(make_session("old", "/repo/a", now - timedelta(days=1)))
+        storage.create_session(make_session("new", "/repo/a", now))
+        storage.create_session(make_session("other", "/repo/b", now))
+
+        recent = storage.most_recent_for_project("/repo/a")
+
+        assert recent is not None
+        assert recent.id == "new"
+
+    def test_recent_for_project_scoped_and_ordered(self, tmp_path):
+        """Test recent_for_project returns only the project, newest first."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        now = datetime.now()
+        storage.create_session(make_session("old", "/repo/a", now - timedelta(days=1)))
+        storage.create_session(make_session("new", "/repo/a", now))
+        storage.create_session(make_session("other", "/repo/b", now))
+
+        recent = storage.recent_for_project("/repo/a")
+
+        assert [s.id for s in recent] == ["new", "old"]
+        assert storage.recent_for_project("/repo/a", limit=1)[0].id == "new"
+
+    def test_most_recent_for_unknown_project(self, tmp_path):
+        """Test that an unknown project yields no session."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+
+        assert storage.most_recent_for_project("/nowhere") is None
+
+    def test_list_sessions_filters_by_tag(self, tmp_path):
+        """Test the tag filter matches tags stored in metadata."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        tagged = make_session("tagged", "/repo/a", datetime.now())
+        tagged.metadata["tags"] = ["refactor"]
+        storage.create_session(tagged)
+        storage.create_session(make_session("plain", "/repo/a", datetime.now()))
+
+        assert [s.id for s in storage.list_sessions(tag="refactor")] == ["tagged"]
+        assert storage.list_sessions(tag="missing") == []
+        assert len(storage.list_sessions()) == 2
+
+    def test_tags_persist_through_update(self, tmp_path):
+        """Test tags edited after creation survive a reload."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        session = make_session("s1", "/repo/a", datetime.now())
+        storage.create_session(session)
+
+        session.metadata["tags"] = ["docs"]
+        storage.update_session(session)
+
+        loaded = storage.get_session("s1")
+        assert loaded is not None
+        assert loaded.metadata["tags"] == ["docs"]
+
+    def test_chat_message_round_trip(self, tmp_path):
+        """Test storing and retrieving chat messages."""
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+        storage.create_session(make_session("s1", "/repo/a", datetime.now()))
+
+        storage.store_chat_message("s1", "user", "hello")
+        storage.store_chat_message("s1", "assistant", "hi there")
+
+        messages = storage.get_messages("s1")
+        assert [m["role"] for m in messages] == ["user", "assistant"]
+        assert messages[1]["content"] == "hi there"
+
+class TestSchemaMigration:
+    """Test schema versioning and corruption recovery."""
+
+    def test_fresh_database_stamped_with_current_version(self, tmp_path):
+        """Test a new database records the current schema version."""
+        from aircher.sessions import SCHEMA_VERSION
+
+        storage = SessionStorage(db_path=tmp_path / "sessions.db")
+
+        with storage._connect() as conn:
+            row = conn.execute("SELECT version FROM schema_version").fetchone()
+        assert row[0] == SCHEMA_VERSION
+
+    def test_legacy_database_migrated_forward(self, tmp_path):
+        """Test a pre-versioning database gains the project column and stamp."""
+        import sqlite3
+
+        from aircher.sessions import SCHEMA_VERSION
+
+        db_path = tmp_path / "sessions.db"
+        conn = sqlite3.connect(db_path)
+        conn.execute(
+            "CREATE TABLE sessions (id TEXT PRIMARY KEY, created_at TIMESTAMP "
+            "NOT NULL, last_activity TIMESTAMP NOT NULL, mode TEXT NOT NULL, "
+            "user_id TEXT, metadata TEXT)"
+        )
+        conn.commit()
+        conn.close()
+
+        storage = SessionStorage(db_path=db_path)
+
+        with storage._connect() as conn:
+            columns = {row[1] for row in conn.execute("PRAGMA table_info(sessions)")}
+            version = conn.execute("SELECT version FROM schema_version").fetchone()[0]
+        assert "project" in columns
+        assert version == SCHEMA_VERSION
+
+    def test_corrupt_database_backed_up_and_reinitialized(self, tmp_path):
+        """Test a non-SQLite file is moved aside instead of blocking startup."""
+        db_path = tmp_path / "sessions.db"
+        db_path.write_bytes(b"definitely not a sqlite database")
+
+        storage = SessionStorage(db_path=db_path)
+
+        assert storage._memory_conn is None
+        assert list(tmp_path.glob("sessions.corrupt-*"))
+        assert storage.create_session(
+            make_session("s1", "/repo/a", datetime.now())
+        )
@@ -0,0 +1,318 @@
+"""Tests for provider streaming parsers."""
+
+import json
+
+import pytest
+
+from aircher.models.streaming import (
+    SSEBuffer,
+    parse_anthropic_event,
+    parse_gemini_chunk,
+    parse_openai_chunk,
+    stream_openai_compatible,
+)
+
+# Recorded OpenAI-style SSE stream (abbreviated fields)
+OPENAI_STREAM = (
+    'data: {"choices":[{"delta":{"content":"Hel"},"finish_reason":null}]}\n\n'
+    'data: {"choices":[{"delta":{"content":"lo"},"finish_reason":null}]}\n\n'
+    'data: {"choices":[{"delta":{},"finish_reason":"stop"}],'
+    '"usage":{"total_tokens":42}}\n\n'
+    "data: [DONE]\n\n"
+)
+
+# Recorded Anthropic event-typed SSE stream (abbreviated)
+ANTHROPIC_STREAM = (
+    'event: content_block_delta\n'
+    'data: {"type":"content_block_delta","delta":{"type":"text_delta","text":"Hi"}}\n\n'
+    'event: message_delta\n'
+    'data: {"type":"message_delta","delta":{"stop_reason":"end_turn"},'
+    '"usage":{"output_tokens":7}}\n\n'
+    'event: message_stop\n'
+    'data: {"type":"message_stop"}\n\n'
+)
+
+
+class TestSSEBuffer:
+    """Test SSE frame reassembly."""
+
+    def test_whole_frames(self):
+        """Test complete frames parse in one feed."""
+        events = SSEBuffer().feed("data: one\n\ndata: two\n\n")
+
+        assert events == [(None, "one"), (None, "two")]
+
+    def test_frame_split_across_reads(self):
+        """Test partial frames are buffered, not dropped."""
+        buffer = SSEBuffer()
+
+        assert buffer.feed('data: {"choices":[{"del') == []
+        events = buffer.feed('ta":{"content":"x"}}]}\n\n')
+
+        assert len(events) == 1
+        assert json.loads(events[0][1])["choices"][0]["delta"]["content"] == "x"
+
+    def test_event_type_captured(self):
+        """Test event: lines pair with their data payloads."""
+        events = SSEBuffer().feed("event: ping\ndata: {}\n\n")
+
+        assert events == [("ping", "{}")]
+
+    def test_crlf_normalized(self):
+        """Test CRLF-delimited streams parse."""
+        events = SSEBuffer().feed("data: hi\r\n\r\n")
+
+        assert events == [(None, "hi")]
+
+
+class TestOpenAIParsing:
+    """Test OpenAI/OpenRouter chunk parsing."""
+
+    def test_full_stream(self):
+        """Test a recorded stream yields content, usage, and done."""
+        buffer = SSEBuffer()
+        chunks = [
+            parse_openai_chunk(payload)
+            for _, payload in buffer.feed(OPENAI_STREAM)
+        ]
+
+        assert "".join(c.content for c in chunks if c) == "Hello"
+        assert chunks[2].finish_reason == "stop"
+        assert chunks[2].tokens_used == 42
+        assert chunks[3].done
+
+    def test_malformed_payload_skipped(self):
+        """Test malformed JSON returns None instead of raising."""
+        assert parse_openai_chunk("{not json") is None
+
+
+class TestAnthropicParsing:
+    """Test Anthropic event-typed parsing."""
+
+    def test_full_stream(self):
+        """Test a recorded stream yields text, stop reason, and done."""
+        buffer = SSEBuffer()
+        chunks = [
+            parse_anthropic_event(event_type, payload)
+            for event_type, payload in buffer.feed(ANTHROPIC_STREAM)
+        ]
+
+        assert chunks[0].content == "Hi"
+        assert chunks[1].finish_reason == "end_turn"
+        assert chunks[1].tokens_used == 7
+        assert chunks[2].done
+
+    def test_ping_ignored(self):
+        """Test non-content events return None."""
+        assert parse_anthropic_event("ping", "{}") is None
+
+
+class TestGeminiParsing:
+    """Test Gemini chunked-JSON parsing."""
+
+    def test_content_chunk(self):
+        """Test text parts concatenate."""
+        payload = json.dumps(
+            {"candidates": [{"content": {"parts": [{"text": "a"}, {"text": "b"}]}}]}
+        )
+        chunk = parse_gemini_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.content == "ab"
+        assert not chunk.done
+
+    def test_final_chunk(self):
+        """Test finish reason and usage mark completion."""
+        payload = json.dumps(
+            {
+                "candidates": [
+                    {"content": {"parts": []}, "finishReason": "STOP"}
+                ],
+                "usageMetadata": {"totalTokenCount": 12},
+            }
+        )
+        chunk = parse_gemini_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.done
+        assert chunk.finish_reason == "STOP"
+        assert chunk.tokens_used == 12
+
+
+class TestReasoningChannel:
+    """Test reasoning/thinking content stays off the answer channel."""
+
+    def test_openai_reasoning_delta(self):
+        """Test reasoning_content deltas land in the reasoning field."""
+        payload = json.dumps(
+            {"choices": [{"delta": {"reasoning_content": "let me think"}}]}
+        )
+        chunk = parse_openai_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.reasoning == "let me think"
+        assert chunk.content == ""
+
+    def test_openai_reasoning_token_usage(self):
+        """Test reasoning tokens are extracted from usage details."""
+        payload = json.dumps(
+            {
+                "choices": [{"delta": {}, "finish_reason": "stop"}],
+                "usage": {
+                    "total_tokens": 100,
+                    "completion_tokens_details": {"reasoning_tokens": 60},
+                },
+            }
+        )
+        chunk = parse_openai_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.tokens_used == 100
+        assert chunk.reasoning_tokens == 60
+
+    def test_anthropic_thinking_delta(self):
+        """Test thinking_delta events carry reasoning, not content."""
+        payload = json.dumps(
+            {
+                "type": "content_block_delta",
+                "delta": {"type": "thinking_delta", "thinking": "hmm"},
+            }
+        )
+        chunk = parse_anthropic_event("content_block_delta", payload)
+
+        assert chunk is not None
+        assert chunk.reasoning == "hmm"
+        assert chunk.content == ""
+
+    def test_gemini_thought_parts_split(self):
+        """Test thought-flagged parts separate from answer parts."""
+        payload = json.dumps(
+            {
+                "candidates": [
+                    {
+                        "content": {
+                            "parts": [
+                                {"text": "plan", "thought": True},
+                                {"text": "answer"},
+                            ]
+                        }
+                    }
+                ]
+            }
+        )
+        chunk = parse_gemini_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.reasoning == "plan"
+        assert chunk.content == "answer"
+
+
+class TestCacheReporting:
+    """Test prompt-cache hit counts surface from stream usage."""
+
+    def test_openai_cached_tokens(self):
+        """Test prompt_tokens_details.cached_tokens is extracted."""
+        payload = json.dumps(
+            {
+                "choices": [{"delta": {}, "finish_reason": "stop"}],
+                "usage": {
+                    "total_tokens": 120,
+                    "prompt_tokens_details": {"cached_tokens": 90},
+                },
+            }
+        )
+        chunk = parse_openai_chunk(payload)
+
+        assert chunk is not None
+        assert chunk.cached_tokens == 90
+
+    def test_anthropic_cache_read_on_message_start(self):
+        """Test message_start cache_read_input_tokens is extracted."""
+        payload = json.dumps(
+            {
+                "type": "message_start",
+                "message": {"usage": {"cache_read_input_tokens": 1500}},
+            }
+        )
+        chunk = parse_anthropic_event("message_start", payload)
+
+        assert chunk is not None
+        assert chunk.cached_tokens == 1500
+
+    def test_message_start_without_cache_is_skipped(self):
+        """Test message_start with no cache usage still yields nothing."""
+        payload = json.dumps({"type": "message_start", "message": {"usage": {}}})
+
+        assert parse_anthropic_event("message_start", payload) is None
+
+
+class _FakeHTTPResponse:
+    """Stream canned bytes back in fixed-size reads."""
+
+    def __init__(self, data: bytes):
+        self._data = data
+        self._pos = 0
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        return False
+
+    def read(self, size: int) -> bytes:
+        chunk = self._data[self._pos : self._pos + size]
+        self._pos += size
+        return chunk
+
+
+class TestStreamOpenAICompatible:
+    """Test the raw SSE streaming client for OpenAI-compatible endpoints."""
+
+    @pytest.mark.asyncio
+    async def test_streams_normalized_chunks(self, monkeypatch):
+        """Test a recorded stream yields StreamChunks through to [DONE]."""
+        captured = {}
+
+        def fake_urlopen(request, timeout=None):
+            captured["url"] = request.full_url
+            captured["body"] = json.loads(request.data.decode())
+            return _FakeHTTPResponse(OPENAI_STREAM.encode())
+
+        monkeypatch.setattr("urllib.request.urlopen", fake_urlopen)
+
+        chunks = [
+            chunk
+            async for chunk in stream_openai_compatible(
+                "http://localhost:11434/v1", "ollama", "llama3.2", "hi"
+            )
+        ]
+
+        assert "".join(c.content for c in chunks) == "Hello"
+        assert chunks[-1].done
+        assert any(c.tokens_used == 42 for c in chunks)
+        assert captured["url"] == "http://localhost:11434/v1/chat/completions"
+        assert captured["body"]["stream"] is True
+        assert captured["body"]["stream_options"] == {"include_usage": True}
+
+    @pytest.mark.asyncio
+    async def test_usage_frame_after_finish_reason_survives(self, monkeypatch):
+        """Test the trailing usage-only frame is not cut off by finish_reason."""
+        stream = (
+            'data: {"choices":[{"delta":{"content":"x"},"finish_reason":"stop"}]}\n\n'
+            'data: {"choices":[],"usage":{"total_tokens":9}}\n\n'
+            "data: [DONE]\n\n"
+        )
+
+        monkeypatch.setattr(
+            "urllib.request.urlopen",
+            lambda request, timeout=None: _FakeHTTPResponse(stream.encode()),
+        )
+
+        chunks = [
+            chunk
+            async for chunk in stream_openai_compatible(
+                "http://localhost:8000/v1", "vllm", "qwen", "hi"
+            )
+        ]
+
+        assert [c.tokens_used for c in chunks] == [None, 9, None]
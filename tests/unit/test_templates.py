@@ -0,0 +1,67 @@
+"""Tests for named prompt templates."""
+
+from aircher.templates import TemplateStore, expand_template
+
+
+class TestTemplateStore:
+    """Test template storage and shadowing."""
+
+    def test_add_and_get(self, tmp_path):
+        """Test round-tripping a project template."""
+        store = TemplateStore(
+            project_dir=tmp_path, global_path=tmp_path / "global.json"
+        )
+        store.add("review", "review this code for bugs: {{cursor}}")
+
+        assert store.get("review") == "review this code for bugs: {{cursor}}"
+
+    def test_project_shadows_global(self, tmp_path):
+        """Test that project templates win over global ones by name."""
+        store = TemplateStore(
+            project_dir=tmp_path, global_path=tmp_path / "global.json"
+        )
+        store.add("review", "global version", scope="global")
+        store.add("review", "project version", scope="project")
+
+        assert store.get("review") == "project version"
+
+    def test_remove(self, tmp_path):
+        """Test removing a template."""
+        store = TemplateStore(
+            project_dir=tmp_path, global_path=tmp_path / "global.json"
+        )
+        store.add("tmp", "content")
+
+        assert store.remove("tmp")
+        assert store.get("tmp") is None
+        assert not store.remove("tmp")
+
+
+class TestExpandTemplate:
+    """Test placeholder expansion."""
+
+    def test_cursor_split(self):
+        """Test splitting at the cursor placeholder."""
+        prefix, suffix = expand_template("before {{cursor}} after")
+
+        assert prefix == "before "
+        assert suffix == " after"
+
+    def test_no_cursor(self):
+        """Test that templates without a cursor are all prefix."""
+        prefix, suffix = expand_template("just a prompt")
+
+        assert prefix == "just a prompt"
+        assert suffix == ""
+
+    def test_file_placeholder(self):
+        """Test {{file}} expanding to an attachment marker."""
+        prefix, _ = expand_template("review {{file}}: {{cursor}}", file_arg="a.py")
+
+        assert prefix == "review @file:a.py: "
+
+    def test_file_placeholder_without_argument(self):
+        """Test {{file}} dropping out when no argument is given."""
+        prefix, _ = expand_template("review {{file}} {{cursor}}")
+
+        assert prefix == "review  "
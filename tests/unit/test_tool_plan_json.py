@@ -0,0 +1,48 @@
+"""Tests for lenient tool-plan JSON parsing."""
+
+import pytest
+
+from aircher.agent import _extract_json_array, lenient_json_loads
+
+
+class TestLenientJsonLoads:
+    """Test repair of the malformations weak models emit."""
+
+    def test_valid_json_passes_through(self):
+        """Test strict JSON parses unchanged."""
+        assert lenient_json_loads('[{"tool": "bash"}]') == [{"tool": "bash"}]
+
+    def test_trailing_commas_repaired(self):
+        """Test dangling commas before closers are removed."""
+        parsed = lenient_json_loads('[{"tool": "bash", "parameters": {},},]')
+
+        assert parsed == [{"tool": "bash", "parameters": {}}]
+
+    def test_unquoted_keys_repaired(self):
+        """Test bare object keys get quoted."""
+        parsed = lenient_json_loads('[{tool: "bash", parameters: {}}]')
+
+        assert parsed == [{"tool": "bash", "parameters": {}}]
+
+    def test_unrecoverable_json_raises(self):
+        """Test hopeless input still raises for the self-correct path."""
+        with pytest.raises(ValueError):
+            lenient_json_loads("[{this is not json")
+
+
+class TestExtractJsonArray:
+    """Test pulling the tool-call array out of a model response."""
+
+    def test_code_fence_preferred(self):
+        """Test a fenced array wins over surrounding prose."""
+        text = 'Here you go:\n```json\n[{"tool": "bash"}]\n```\nDone.'
+
+        assert _extract_json_array(text) == '[{"tool": "bash"}]'
+
+    def test_bare_array_found(self):
+        """Test an unfenced array is still located."""
+        assert _extract_json_array('plan: [{"tool": "bash"}]') == '[{"tool": "bash"}]'
+
+    def test_no_array_defaults_empty(self):
+        """Test prose without an array yields an empty plan."""
+        assert _extract_json_array("No tools needed.") == "[]"
@@ -0,0 +1,37 @@
+"""Tests for the JSONL tool-call trace."""
+
+import json
+
+from aircher.agent.trace import ToolTracer
+
+
+class TestToolTracer:
+    """Test trace recording and the enabled gate."""
+
+    def test_disabled_writes_nothing(self, tmp_path):
+        """Test no file appears while tracing is off."""
+        tracer = ToolTracer(project_dir=tmp_path)
+        tracer.record({"type": "tool_call", "tool": "read_file"})
+
+        assert not tracer.trace_path.exists()
+
+    def test_events_append_as_jsonl(self, tmp_path):
+        """Test enabled tracing appends one JSON object per line."""
+        tracer = ToolTracer(project_dir=tmp_path, enabled=True)
+        tracer.record({"type": "tool_call", "tool": "read_file", "duration_ms": 3})
+        tracer.record({"type": "tool_call", "tool": "bash", "error": "boom"})
+
+        lines = tracer.trace_path.read_text().splitlines()
+        events = [json.loads(line) for line in lines]
+
+        assert [e["tool"] for e in events] == ["read_file", "bash"]
+        assert all("timestamp" in e for e in events)
+        assert events[1]["error"] == "boom"
+
+    def test_non_serializable_values_stringified(self, tmp_path):
+        """Test results that aren't JSON-native don't break the trace."""
+        tracer = ToolTracer(project_dir=tmp_path, enabled=True)
+        tracer.record({"type": "tool_call", "result": {"path": tmp_path}})
+
+        event = json.loads(tracer.trace_path.read_text())
+        assert str(tmp_path) in event["result"]["path"]
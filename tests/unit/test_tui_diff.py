@@ -0,0 +1,54 @@
+"""Tests for diff rendering palettes."""
+
+from rich.console import Console
+
+from aircher.tui.diff import PALETTES, DiffViewer, generate_diff
+
+
+class TestGenerateDiff:
+    """Test unified diff generation."""
+
+    def test_basic_diff(self):
+        """Test added and removed lines appear with prefixes."""
+        lines = generate_diff("a\nb\n", "a\nc\n", path="f.txt")
+
+        assert any(line.startswith("-b") for line in lines)
+        assert any(line.startswith("+c") for line in lines)
+        assert lines[0].startswith("--- a/f.txt")
+
+    def test_no_changes(self):
+        """Test identical content yields no diff lines."""
+        assert generate_diff("same\n", "same\n") == []
+
+
+class TestDiffViewer:
+    """Test palette selection."""
+
+    def test_default_palette_uses_red_green(self):
+        """Test default styles stay on the red/green axis."""
+        viewer = DiffViewer(Console(), palette="default")
+
+        assert viewer._style_for("+added") == "green"
+        assert viewer._style_for("-removed") == "red"
+
+    def test_colorblind_palette_avoids_red_green(self):
+        """Test the colorblind palette swaps to blue/orange."""
+        viewer = DiffViewer(Console(), palette="colorblind")
+
+        assert viewer._style_for("+added") == PALETTES["colorblind"]["add"]
+        assert viewer._style_for("-removed") == PALETTES["colorblind"]["delete"]
+        assert "red" not in viewer._style_for("-removed")
+        assert "green" not in viewer._style_for("+added")
+
+    def test_unknown_palette_falls_back(self):
+        """Test unknown palette names fall back to the default."""
+        viewer = DiffViewer(Console(), palette="nope")
+
+        assert viewer.palette == PALETTES["default"]
+
+    def test_backup_diff_missing_backup(self, tmp_path):
+        """Test diffing without a backup reports failure."""
+        path = tmp_path / "f.txt"
+        path.write_text("content\n")
+
+        assert not DiffViewer(Console()).render_file_backup(path)
@@ -0,0 +1,140 @@
+"""Tests for the TUI streaming status display."""
+
+from aircher.tui.display import (
+    HUSTLING_MESSAGES,
+    MAX_CONTENT_WIDTH,
+    MIN_TERMINAL_WIDTH,
+    PLAIN_MESSAGES,
+    get_streaming_display,
+    layout_width,
+    split_fenced_blocks,
+    terminal_capabilities,
+)
+
+
+class TestStreamingDisplay:
+    """Test status line assembly for each style."""
+
+    def test_fun_style_uses_themed_messages(self):
+        """Test the default style shows themed messages."""
+        display = get_streaming_display(0.0, phase="thinking", style="fun")
+
+        assert HUSTLING_MESSAGES[0] in display
+        assert "(0s)" in display
+
+    def test_fun_style_rotates_messages(self):
+        """Test themed messages rotate every 3 seconds."""
+        first = get_streaming_display(0.0, style="fun")
+        second = get_streaming_display(3.0, style="fun")
+
+        assert HUSTLING_MESSAGES[0] in first
+        assert HUSTLING_MESSAGES[1] in second
+
+    def test_plain_style(self):
+        """Test plain style shows simple status words."""
+        display = get_streaming_display(5.0, phase="streaming", style="plain")
+
+        assert PLAIN_MESSAGES["streaming"] in display
+        assert all(message not in display for message in HUSTLING_MESSAGES)
+
+    def test_minimal_style(self):
+        """Test minimal style shows only spinner and elapsed time."""
+        display = get_streaming_display(7.0, style="minimal")
+
+        assert "7s" in display
+        assert all(message not in display for message in HUSTLING_MESSAGES)
+        assert "Thinking" not in display
+
+
+class TestLayoutWidth:
+    """Test responsive content width."""
+
+    def test_wide_terminal_capped(self):
+        """Test ultra-wide terminals cap at the maximum content width."""
+        assert layout_width(300) == MAX_CONTENT_WIDTH
+
+    def test_preferred_width_honored(self):
+        """Test a narrower preference wins when there is room."""
+        assert layout_width(120, preferred=55) == 55
+
+    def test_narrow_terminal_shrinks(self):
+        """Test narrow terminals leave a margin."""
+        assert layout_width(40, preferred=55) == 38
+
+    def test_minimum_floor(self):
+        """Test the floor applies once the terminal can actually fit it."""
+        assert layout_width(MIN_TERMINAL_WIDTH) == MIN_TERMINAL_WIDTH - 2
+
+    def test_tiny_terminal_never_overflows(self):
+        """Test widths clamp to the window instead of exceeding it."""
+        assert layout_width(10) == 8
+        assert layout_width(2) == 1
+
+    def test_unknown_width_uses_preferred(self):
+        """Test non-positive widths fall back to the preference."""
+        assert layout_width(0, preferred=55) == 55
+
+    def test_wider_preference_raises_the_cap(self):
+        """Test a configured preference above the default cap is honored."""
+        assert layout_width(300, preferred=140) == 140
+
+
+class TestSplitFencedBlocks:
+    """Test code-block extraction for syntax highlighting."""
+
+    def test_plain_prose(self):
+        """Test prose-only content yields a single prose segment."""
+        assert split_fenced_blocks("just text") == [("just text", None)]
+
+    def test_hinted_block(self):
+        """Test a language hint is carried on the code segment."""
+        content = "before\n```rust\nfn main() {}\n```\nafter"
+
+        segments = split_fenced_blocks(content)
+
+        assert segments == [
+            ("before", None),
+            ("fn main() {}", "rust"),
+            ("after", None),
+        ]
+
+    def test_unhinted_block_flags_fallback(self):
+        """Test a bare fence yields an empty language for fallback."""
+        segments = split_fenced_blocks("```\nx = 1\n```")
+
+        assert segments == [("x = 1", "")]
+
+
+class TestTerminalCapabilities:
+    """Test degraded-terminal detection."""
+
+    def test_capable_terminal(self):
+        """Test a normal UTF-8 xterm keeps color and Unicode."""
+        caps = terminal_capabilities(env={"TERM": "xterm-256color"}, encoding="utf-8")
+
+        assert caps["color"]
+        assert caps["unicode"]
+
+    def test_no_color_convention(self):
+        """Test NO_COLOR disables color but not Unicode."""
+        caps = terminal_capabilities(
+            env={"TERM": "xterm", "NO_COLOR": "1"}, encoding="utf-8"
+        )
+
+        assert not caps["color"]
+        assert caps["unicode"]
+
+    def test_dumb_terminal_degrades_fully(self):
+        """Test TERM=dumb drops both color and Unicode."""
+        caps = terminal_capabilities(env={"TERM": "dumb"}, encoding="utf-8")
+
+        assert not caps["color"]
+        assert not caps["unicode"]
+
+    def test_non_utf_encoding_drops_unicode(self):
+        """Test an ASCII-only encoding falls back to the ASCII spinner."""
+        caps = terminal_capabilities(env={"TERM": "xterm"}, encoding="ascii")
+
+        assert not caps["unicode"]
+        line = get_streaming_display(0.0, style="minimal", unicode=False)
+        assert line[0] in "|/-\\"
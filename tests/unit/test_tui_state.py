@@ -0,0 +1,86 @@
+"""Tests for TUI UI-state persistence."""
+
+from aircher.tui.state import UIState, UIStateStore
+
+
+class TestUIState:
+    """Test UI state model."""
+
+    def test_defaults(self):
+        """Test default state values."""
+        state = UIState()
+
+        assert state.input == ""
+        assert state.mode == "read"
+        assert state.scroll_offset == 0
+        assert state.session_id is None
+        assert state.welcome_dismissed is False
+
+    def test_welcome_dismissal_round_trips(self, tmp_path):
+        """Test the dismissed-banner flag persists through the store."""
+        store = UIStateStore(project_dir=tmp_path)
+        store.save(UIState(welcome_dismissed=True))
+
+        loaded = store.load()
+        assert loaded is not None
+        assert loaded.welcome_dismissed is True
+
+
+class TestUIStateStore:
+    """Test UI state persistence."""
+
+    def test_save_and_load(self, tmp_path):
+        """Test round-tripping state through the store."""
+        store = UIStateStore(project_dir=tmp_path)
+        state = UIState(input="draft message", mode="edit", session_id="s1")
+
+        assert store.save(state) is True
+
+        loaded = store.load()
+        assert loaded is not None
+        assert loaded.input == "draft message"
+        assert loaded.mode == "edit"
+        assert loaded.session_id == "s1"
+
+    def test_load_missing_returns_none(self, tmp_path):
+        """Test loading when nothing was saved."""
+        store = UIStateStore(project_dir=tmp_path)
+
+        assert store.load() is None
+
+    def test_load_corrupt_returns_none(self, tmp_path):
+        """Test loading corrupt state fails gracefully."""
+        store = UIStateStore(project_dir=tmp_path)
+        store.state_path.parent.mkdir(parents=True)
+        store.state_path.write_text("not json{")
+
+        assert store.load() is None
+
+    def test_maybe_save_respects_interval(self, tmp_path):
+        """Test throttled saves only fire after the interval."""
+        store = UIStateStore(project_dir=tmp_path, interval_seconds=3600)
+        state = UIState(input="first")
+
+        # First throttled save fires (nothing saved yet)
+        assert store.maybe_save(state) is True
+        # Second is throttled by the interval
+        assert store.maybe_save(UIState(input="second")) is False
+
+        loaded = store.load()
+        assert loaded.input == "first"
+
+    def test_maybe_save_disabled(self, tmp_path):
+        """Test interval of 0 disables periodic autosave."""
+        store = UIStateStore(project_dir=tmp_path, interval_seconds=0)
+
+        assert store.maybe_save(UIState()) is False
+        assert store.load() is None
+
+    def test_clear(self, tmp_path):
+        """Test clearing persisted state."""
+        store = UIStateStore(project_dir=tmp_path)
+        store.save(UIState(input="draft"))
+
+        store.clear()
+
+        assert store.load() is None
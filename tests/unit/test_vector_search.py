@@ -5,7 +5,7 @@ from pathlib import Path
 
 import pytest
 
-from aircher.memory.vector_search import VectorSearch
+from aircher.memory.vector_search import VectorSearch, nearest_symbol
 
 
 @pytest.fixture
@@ -341,6 +341,29 @@ class TestCodebaseIndexing:
         # Should create multiple chunks
         assert stats["snippets_indexed"] > 1
 
+    @pytest.mark.asyncio
+    async def test_reindex_path_replaces_chunks(self, tmp_path, vector_search):
+        """Test reindexing a file replaces its chunks instead of appending."""
+        target = tmp_path / "mod.py"
+        target.write_text("def one(): pass")
+        await vector_search.index_codebase(tmp_path, languages=[".py"])
+        before = vector_search.count()
+
+        stats = await vector_search.reindex_path(target)
+
+        assert stats == {"files_indexed": 1, "errors": 0}
+        assert vector_search.count() == before
+
+    @pytest.mark.asyncio
+    async def test_reindex_directory(self, tmp_path, vector_search):
+        """Test reindexing a directory covers its recognized files."""
+        (tmp_path / "a.py").write_text("def a(): pass")
+        (tmp_path / "b.py").write_text("def b(): pass")
+
+        stats = await vector_search.reindex_path(tmp_path)
+
+        assert stats["files_indexed"] == 2
+
     @pytest.mark.asyncio
     async def test_index_multiple_languages(self, tmp_path, vector_search):
         """Test indexing multiple language files."""
@@ -514,3 +537,176 @@ class TestEdgeCases:
         )
 
         assert len(results) == 0
+
+
+class TestDocumentationBoost:
+    """Test the optional documented-chunk ranking boost."""
+
+    def test_conceptual_query_detection(self):
+        """Test conceptual vs symbol-ish query classification."""
+        assert VectorSearch.is_conceptual_query("how does session loading work")
+        assert VectorSearch.is_conceptual_query("explain error handling")
+        assert not VectorSearch.is_conceptual_query("create_session")
+        assert not VectorSearch.is_conceptual_query("fn main()")
+        assert not VectorSearch.is_conceptual_query("config")
+
+    def test_doc_comment_density_python(self):
+        """Test docstring density calculation for Python."""
+        documented = '"""Docstring."""\ndef f():\n    # comment\n    pass'
+        bare = "def f():\n    pass"
+
+        assert VectorSearch.doc_comment_density(documented, "python") > 0
+        assert VectorSearch.doc_comment_density(bare, "python") == 0.0
+
+    def test_doc_comment_density_unknown_language(self):
+        """Test unknown languages get no boost."""
+        assert VectorSearch.doc_comment_density('"""doc"""', "unknown") == 0.0
+
+    def test_prefer_documented_reorders(self, vector_search):
+        """Test documented chunks rank higher with the boost enabled."""
+        vector_search.index_code_snippet(
+            file_path="/src/bare.py",
+            content="def load_session(sid):\n    return db.get(sid)",
+            start_line=1,
+            end_line=2,
+            language="python",
+        )
+        vector_search.index_code_snippet(
+            file_path="/src/documented.py",
+            content=(
+                '"""Load a session from storage by its id."""\n'
+                "def load_session(sid):\n    return db.get(sid)"
+            ),
+            start_line=1,
+            end_line=3,
+            language="python",
+        )
+
+        results = vector_search.search(
+            "how does session loading work", n_results=2, prefer_documented=True
+        )
+
+        assert len(results) == 2
+        boosted = [r for r in results if r.get("doc_boost")]
+        assert len(boosted) >= 1
+        assert all(r["metadata"]["file_path"] == "/src/documented.py" for r in boosted)
+
+
+class TestRerankParsing:
+    """Test LLM rerank score parsing."""
+
+    def test_plain_array(self):
+        """Test a bare JSON array parses."""
+        scores = VectorSearch._parse_rerank_scores("[8, 3.5, 10]", 3)
+
+        assert scores == [8.0, 3.5, 10.0]
+
+    def test_array_in_prose(self):
+        """Test arrays wrapped in prose or code fences parse."""
+        text = "Here are the scores:\n```json\n[1, 2]\n```"
+
+        assert VectorSearch._parse_rerank_scores(text, 2) == [1.0, 2.0]
+
+    def test_wrong_length_rejected(self):
+        """Test arrays of the wrong length are rejected."""
+        assert VectorSearch._parse_rerank_scores("[1, 2]", 3) is None
+
+    def test_non_numeric_rejected(self):
+        """Test non-numeric arrays are rejected."""
+        assert VectorSearch._parse_rerank_scores('["a", "b"]', 2) is None
+
+
+class TestIndexStatus:
+    """Test per-file freshness reporting."""
+
+    @pytest.mark.asyncio
+    async def test_fresh_and_stale_files(self, vector_search, tmp_path):
+        """Test stale detection after a file changes on disk."""
+        fresh = tmp_path / "fresh.py"
+        fresh.write_text("def a():\n    pass\n")
+        stale = tmp_path / "stale.py"
+        stale.write_text("def b():\n    pass\n")
+
+        await vector_search.index_codebase(tmp_path)
+
+        import os
+        future = stale.stat().st_mtime + 10
+        os.utime(stale, (future, future))
+
+        report = {e["file_path"]: e for e in vector_search.index_status()}
+        assert not report[str(fresh)]["stale"]
+        assert report[str(stale)]["stale"]
+        assert report[str(fresh)]["chunks"] >= 1
+
+    @pytest.mark.asyncio
+    async def test_missing_file_flagged(self, vector_search, tmp_path):
+        """Test deleted files report missing and stale."""
+        path = tmp_path / "gone.py"
+        path.write_text("def c():\n    pass\n")
+        await vector_search.index_codebase(tmp_path)
+        path.unlink()
+
+        report = {e["file_path"]: e for e in vector_search.index_status()}
+        assert report[str(path)]["missing"]
+        assert report[str(path)]["stale"]
+
+
+class TestEmbeddingModelStamp:
+    """Test embedding-model mismatch detection."""
+
+    def test_collection_stamped_with_model(self, vector_search):
+        """Test the collection records the embedding model and dimension."""
+        metadata = vector_search.collection.metadata
+
+        assert metadata["embedding_model"] == vector_search.model_name
+        assert (
+            metadata["embedding_dimension"]
+            == vector_search.model.get_sentence_embedding_dimension()
+        )
+
+    def test_mismatch_raises_with_guidance(self, vector_search):
+        """Test a stamped index from another model refuses to load."""
+        vector_search.index_code_snippet(
+            file_path="a.py",
+            content="def a(): pass",
+            start_line=1,
+            end_line=1,
+            language="python",
+        )
+        # Simulate an index built by a different model
+        vector_search.model_name = "sentence-transformers/other-model"
+
+        with pytest.raises(RuntimeError, match="search index --force"):
+            vector_search._verify_embedding_model()
+
+    def test_empty_index_accepts_any_model(self, vector_search):
+        """Test an empty index never blocks a model change."""
+        vector_search.model_name = "sentence-transformers/other-model"
+
+        vector_search._verify_embedding_model()
+
+
+class TestNearestSymbol:
+    """Test symbol attribution for indexed chunks."""
+
+    def test_definition_inside_chunk(self):
+        """Test the first definition within the chunk wins."""
+        lines = ["import os", "", "def parse_config():", "    return 1"]
+
+        assert nearest_symbol(lines, 0, 4, "python") == "parse_config"
+
+    def test_body_continuation_uses_enclosing_symbol(self):
+        """Test a chunk without definitions inherits the one above it."""
+        lines = ["def outer():", "    a = 1", "    b = 2", "    return a + b"]
+
+        assert nearest_symbol(lines, 2, 4, "python") == "outer"
+
+    def test_rust_function(self):
+        """Test rust fn definitions are recognized."""
+        lines = ["pub async fn handle_event(e: Event) {", "}"]
+
+        assert nearest_symbol(lines, 0, 2, "rust") == "handle_event"
+
+    def test_unknown_language_returns_none(self):
+        """Test languages without patterns are skipped."""
+        assert nearest_symbol(["SELECT 1;"], 0, 1, "sql") is None
@@ -0,0 +1,61 @@
+"""Tests for the web search tool."""
+
+import pytest
+
+from aircher.modes import MODE_CAPABILITIES, AgentMode
+from aircher.tools.web_search import (
+    SEARCH_API_KEY_ENV,
+    WebSearchTool,
+    web_search_available,
+)
+
+
+class TestWebSearchAvailability:
+    """Test the API-key gate for web search."""
+
+    def test_unavailable_without_key(self, monkeypatch):
+        """Test availability is false when the key env var is unset."""
+        monkeypatch.delenv(SEARCH_API_KEY_ENV, raising=False)
+
+        assert not web_search_available()
+
+    def test_available_with_key(self, monkeypatch):
+        """Test availability is true once the key is set."""
+        monkeypatch.setenv(SEARCH_API_KEY_ENV, "test-key")
+
+        assert web_search_available()
+
+
+class TestWebSearchTool:
+    """Test web search execution guards."""
+
+    @pytest.mark.asyncio
+    async def test_empty_query_fails(self):
+        """Test an empty query is rejected before any request."""
+        result = await WebSearchTool().execute(query="   ")
+
+        assert not result.success
+        assert "query" in result.error.lower()
+
+    @pytest.mark.asyncio
+    async def test_missing_key_fails_clearly(self, monkeypatch):
+        """Test running without a key names the env var in the error."""
+        monkeypatch.delenv(SEARCH_API_KEY_ENV, raising=False)
+
+        result = await WebSearchTool().execute(query="httpx timeouts")
+
+        assert not result.success
+        assert SEARCH_API_KEY_ENV in result.error
+
+
+class TestNetworkCapability:
+    """Test the network capability gating web search per mode."""
+
+    def test_read_mode_is_offline(self):
+        """Test READ mode never gets network access."""
+        assert not MODE_CAPABILITIES[AgentMode.READ].can_access_network
+
+    def test_edit_and_turbo_have_network(self):
+        """Test EDIT and TURBO modes allow network tools."""
+        assert MODE_CAPABILITIES[AgentMode.EDIT].can_access_network
+        assert MODE_CAPABILITIES[AgentMode.TURBO].can_access_network